@@ -7,9 +7,12 @@ use napi::{
     threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode},
     JsFunction,
 };
-use next_core::app_structure::{
-    find_app_dir, get_entrypoints as get_entrypoints_impl, Components, Entrypoint, Entrypoints,
-    LoaderTree, MetadataItem, MetadataWithAltItem,
+use next_core::{
+    app_structure::{
+        find_app_dir, get_entrypoints as get_entrypoints_impl, Components, Entrypoint, Entrypoints,
+        LoaderTree, MetadataItem, MetadataWithAltItem,
+    },
+    next_config::MetadataConflictPolicy,
 };
 use serde::{Deserialize, Serialize};
 use turbo_tasks::{
@@ -352,7 +355,11 @@ async fn get_value(
     let app_dir = find_app_dir(project_path);
 
     let result = if let Some(app_dir) = *app_dir.await? {
-        let entrypoints = get_entrypoints_impl(app_dir, page_extensions);
+        let entrypoints = get_entrypoints_impl(
+            app_dir,
+            page_extensions,
+            MetadataConflictPolicy::default().cell(),
+        );
         let entrypoints_for_js = prepare_entrypoints_for_js(project_path, entrypoints);
 
         Some(entrypoints_for_js)
@@ -72,6 +72,10 @@ impl From<Option<WrittenEndpoint>> for NapiWrittenEndpoint {
                 server_paths: server_paths.into_iter().map(From::from).collect(),
                 ..Default::default()
             },
+            Some(WrittenEndpoint::Cancelled) => Self {
+                r#type: "cancelled".to_string(),
+                ..Default::default()
+            },
             None => Self {
                 r#type: "none".to_string(),
                 ..Default::default()
@@ -167,6 +171,22 @@ pub async fn endpoint_write_to_disk(
     })
 }
 
+#[napi]
+#[tracing::instrument(skip_all)]
+pub async fn endpoint_invalidate(
+    #[napi(ts_arg_type = "{ __napiType: \"Endpoint\" }")] endpoint: External<ExternalEndpoint>,
+) -> napi::Result<()> {
+    let turbo_tasks = endpoint.turbo_tasks().clone();
+    let endpoint = ***endpoint;
+    turbo_tasks
+        .run_once(async move {
+            endpoint.invalidate().await?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+}
+
 #[napi(ts_return_type = "{ __napiType: \"RootTask\" }")]
 pub fn endpoint_server_changed_subscribe(
     #[napi(ts_arg_type = "{ __napiType: \"Endpoint\" }")] endpoint: External<ExternalEndpoint>,
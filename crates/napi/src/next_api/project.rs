@@ -7,6 +7,7 @@ use napi::{
     JsFunction, Status,
 };
 use next_api::{
+    build_stats::register_task_statistics,
     entrypoints::Entrypoints,
     project::{
         DefineEnv, DraftModeOptions, Instrumentation, Middleware, PartialProjectOptions, Project,
@@ -325,6 +326,7 @@ pub async fn project_new(
     let stats_path = std::env::var_os("NEXT_TURBOPACK_TASK_STATISTICS");
     if let Some(stats_path) = stats_path {
         let task_stats = turbo_tasks.backend().task_statistics().enable().clone();
+        register_task_statistics(task_stats.clone());
         exit.on_exit(async move {
             tokio::task::spawn_blocking(move || {
                 let mut file = std::fs::File::create(&stats_path)
@@ -439,6 +441,27 @@ pub async fn project_update(
     Ok(())
 }
 
+/// Writes `server/compressed-manifests.json` to the node root when
+/// `compressManifests` is enabled. See [Project::write_compressed_manifests].
+#[napi]
+pub async fn project_write_compressed_manifests(
+    #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
+) -> napi::Result<()> {
+    let turbo_tasks = project.turbo_tasks.clone();
+    turbo_tasks
+        .run_once(async move {
+            project
+                .container
+                .project()
+                .write_compressed_manifests()
+                .strongly_consistent()
+                .await?;
+            Ok(())
+        })
+        .await
+        .map_err(|e| napi::Error::from_reason(PrettyPrintError(&e).to_string()))
+}
+
 #[napi(ts_return_type = "{ __napiType: \"Project\" }")]
 pub async fn project_shutdown(
     #[napi(ts_arg_type = "{ __napiType: \"Project\" }")] project: External<ProjectInstance>,
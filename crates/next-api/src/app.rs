@@ -1,8 +1,10 @@
-use anyhow::{Context, Result};
-use indexmap::IndexSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::{bail, Context, Result};
+use indexmap::{indexmap, IndexMap, IndexSet};
 use next_core::{
     all_assets_from_entries,
-    app_segment_config::NextSegmentConfig,
+    app_segment_config::{NextSegmentConfig, NextSegmentDynamic},
     app_structure::{
         get_entrypoints, Entrypoint as AppEntrypoint, Entrypoints as AppEntrypoints, LoaderTree,
         MetadataItem,
@@ -10,22 +12,34 @@ use next_core::{
     get_edge_resolve_options_context, get_next_package,
     next_app::{
         app_client_references_chunks::get_app_server_reference_modules,
+        app_route_methods::{get_app_route_methods, AppRouteMethods},
         get_app_client_references_chunks, get_app_client_shared_chunk_group, get_app_page_entry,
-        get_app_route_entry, metadata::route::get_app_metadata_route_entry, AppEntry, AppPage,
+        get_app_route_entry,
+        include_modules_module::IncludeModulesModule,
+        metadata::{
+            match_global_metadata_file, match_local_metadata_file, normalize_metadata_route,
+            route::get_app_metadata_route_entry, MetadataContentTypeResolver,
+        },
+        AppEntry, AppPage, AppPath,
     },
     next_client::{
         get_client_module_options_context, get_client_resolve_options_context,
-        get_client_runtime_entries, ClientContextType, RuntimeEntries,
+        get_client_runtime_entries, ClientContextType, RuntimeEntries, RuntimeEntry,
     },
     next_client_reference::{
         client_reference_graph, ClientReferenceType, NextEcmascriptClientReferenceTransition,
     },
-    next_config::NextConfig,
+    next_config::{AppPathsManifestKey, ClientReferenceCyclesMode, NextConfig},
     next_dynamic::NextDynamicTransition,
-    next_edge::route_regex::get_named_middleware_regex,
+    next_edge::route_regex::{get_named_middleware_regex, get_route_regex, with_base_path},
     next_manifests::{
-        AppBuildManifest, AppPathsManifest, BuildManifest, ClientReferenceManifest,
-        EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2, PagesManifest, Regions,
+        transform_asset_path, AppBuildManifest, AppPathsManifest, AppRevalidateManifest,
+        AssetPathTransform, BuildManifest, ChunkRoutesIndex, ClientReferenceManifest,
+        CssPreloadManifest, DynamicPrerenderRoute, EdgeFunctionDefinition, ErrorPreloadManifest,
+        FederationExposedModule, FederationManifest, HeadersManifest, MiddlewareMatcher,
+        MiddlewaresManifest, MiddlewaresManifestV1, MiddlewaresManifestV2, PagesManifest,
+        PrerenderManifest, PrerenderRoute, PreviewManifest, Regions, ServerActionRoutes,
+        ServerActionsRoutesManifest, SourcesManifest, StaticRoutesManifest, WasmInventoryManifest,
     },
     next_server::{
         get_server_module_options_context, get_server_resolve_options_context,
@@ -36,9 +50,17 @@ use next_core::{
 };
 use serde::{Deserialize, Serialize};
 use tracing::Instrument;
-use turbo_tasks::{trace::TraceRawVcs, Completion, RcStr, TryJoinIterExt, Value, Vc};
+use turbo_tasks::{
+    debug::ValueDebugFormat, run_once, trace::TraceRawVcs, turbo_tasks, util::FormatBytes,
+    Completion, RcStr, ReadRef, State, TryJoinIterExt, Value, ValueToString, Vc,
+};
 use turbo_tasks_env::{CustomProcessEnv, ProcessEnv};
-use turbo_tasks_fs::{File, FileContent, FileSystemPath};
+use turbo_tasks_fs::{
+    json::parse_json_rope_with_source_context, DirectoryContent, DirectoryEntry, File, FileContent,
+    FileSystemPath,
+};
+use turbo_tasks_hash::{encode_hex, Xxh3Hash64Hasher};
+use turbo_tasks_malloc::TurboMalloc;
 use turbopack::{
     module_options::ModuleOptionsContext,
     resolve_options_context::ResolveOptionsContext,
@@ -48,42 +70,254 @@ use turbopack::{
 use turbopack_core::{
     asset::AssetContent,
     chunk::{
-        availability_info::AvailabilityInfo, ChunkingContext, ChunkingContextExt,
-        EntryChunkGroupResult, EvaluatableAssets,
+        availability_info::AvailabilityInfo, ChunkGroupResult, ChunkableModule, ChunkingContext,
+        ChunkingContextExt, EntryChunkGroupResult, EvaluatableAssets,
     },
+    context::AssetContext,
     file_source::FileSource,
     ident::AssetIdent,
-    issue::IssueSeverity,
+    issue::{IssueDescriptionExt, IssueExt, IssueSeverity, StyledString},
     module::{Module, Modules},
     output::{OutputAsset, OutputAssets},
     raw_output::RawOutput,
-    resolve::{origin::PlainResolveOrigin, parse::Request, pattern::Pattern},
+    reference::{all_modules_and_affecting_sources, primary_referenced_modules},
+    reference_type::{EntryReferenceSubType, ReferenceType},
+    resolve::{origin::PlainResolveOrigin, parse::Request, pattern::Pattern, resolve},
     source::Source,
     virtual_output::VirtualOutputAsset,
 };
 use turbopack_ecmascript::resolve::cjs_resolve;
 
 use crate::{
+    client_references::{
+        client_reference_cycles, client_reference_error_boundaries, client_reference_root_layouts,
+        duplicate_client_chunk_modules, ClientReferenceCycleIssue, DuplicateClientModuleIssue,
+        RootLayoutClientReferenceIssue,
+    },
+    compact_json::compact_json_manifests,
     dynamic_imports::{
         collect_chunk_group, collect_evaluated_chunk_group, collect_next_dynamic_imports,
     },
-    font::create_font_manifest,
-    loadable_manifest::create_react_loadable_manifest,
+    edge_size::{total_size, EdgeFunctionSizeIssue},
+    env_manifest::create_env_manifest,
+    font::{create_font_manifest, used_font_paths},
+    gzip::gzip_manifests,
+    loadable_manifest::{create_dynamic_imports_manifest, create_react_loadable_manifest},
+    node_builtins::{edge_node_builtin_imports, EdgeNodeBuiltinImportIssue},
+    openapi::create_openapi_stub,
     paths::{
-        all_paths_in_root, all_server_paths, get_js_paths_from_root, get_paths_from_root,
-        get_wasm_paths_from_root, paths_to_bindings, wasm_paths_to_bindings,
+        all_paths_in_root, all_server_paths, diff_output_assets, get_js_paths_from_root,
+        get_path_in_root, get_paths_from_root, get_wasm_paths_from_root, paths_to_bindings,
+        sanitize_route_path, wasm_paths_to_bindings, OutputAssetsDiff,
+    },
+    post_process::{inject_debug_ids, post_process_output_assets, OutputAssetPostProcessor},
+    project::{ConflictIssue, Project},
+    route::{
+        endpoint_revision, invalidate_endpoint, AppEndpointKind, AppPageRoute, EdgeFunctionMatcher,
+        EdgeFunctionMatchers, Endpoint, EndpointMiddlewareManifest, EndpointRevision, Route,
+        Routes, WrittenEndpoint,
     },
-    project::Project,
-    route::{AppPageRoute, Endpoint, Route, Routes, WrittenEndpoint},
-    server_actions::create_server_actions_manifest,
+    server_actions::{create_server_actions_manifest, server_action_ids as get_server_action_ids},
+    stats::create_route_stats,
 };
 
+/// Aborts endpoint builds that are still in flight, e.g. because a file changed
+/// while they were running and their output would be discarded anyway.
+///
+/// Builds register their endpoint with the token while they run and check at
+/// checkpoints between their major phases whether the revision of their
+/// endpoint changed. Cancelling bumps the revision of the registered endpoints
+/// only, so their builds return [AppEndpointOutput::Cancelled] at the next
+/// checkpoint and are re-executed the next time their output is read. Finished
+/// builds keep their output.
+#[turbo_tasks::value]
+pub struct CancellationToken {
+    /// The number of builds in flight, by endpoint.
+    in_flight: State<HashMap<Vc<AppEndpoint>, usize>>,
+}
+
+impl CancellationToken {
+    fn new() -> Vc<Self> {
+        CancellationToken {
+            in_flight: State::new(HashMap::new()),
+        }
+        .cell()
+    }
+
+    /// Cancels all builds that are currently in flight.
+    async fn cancel(&self) -> Result<()> {
+        let endpoints = self
+            .in_flight
+            .get_untracked()
+            .keys()
+            .copied()
+            .collect::<Vec<_>>();
+        for endpoint in endpoints {
+            endpoint_revision(Vc::upcast(endpoint)).await?.bump();
+        }
+        Ok(())
+    }
+}
+
+/// A build of an app endpoint, registered with the [CancellationToken] of its
+/// project until it's dropped.
+struct InFlightBuild {
+    cancellation_token: ReadRef<CancellationToken>,
+    endpoint: Vc<AppEndpoint>,
+    endpoint_revision: ReadRef<EndpointRevision>,
+    revision: u64,
+}
+
+impl InFlightBuild {
+    async fn start(endpoint: Vc<AppEndpoint>) -> Result<Self> {
+        let endpoint_revision = endpoint_revision(Vc::upcast(endpoint)).await?;
+        // Makes the build depend on the revision, so invalidating or cancelling the
+        // endpoint re-executes it.
+        let revision = endpoint_revision.track();
+        let cancellation_token = endpoint
+            .await?
+            .app_project
+            .await?
+            .cancellation_token
+            .await?;
+        cancellation_token
+            .in_flight
+            .update_conditionally(|in_flight| {
+                *in_flight.entry(endpoint).or_default() += 1;
+                true
+            });
+        Ok(InFlightBuild {
+            cancellation_token,
+            endpoint,
+            endpoint_revision,
+            revision,
+        })
+    }
+
+    /// Whether the build was cancelled, or its endpoint invalidated, since it
+    /// started.
+    fn is_cancelled(&self) -> bool {
+        self.endpoint_revision.current() != self.revision
+    }
+}
+
+impl Drop for InFlightBuild {
+    fn drop(&mut self) {
+        self.cancellation_token
+            .in_flight
+            .update_conditionally(|in_flight| {
+                let Some(count) = in_flight.get_mut(&self.endpoint) else {
+                    return false;
+                };
+                *count -= 1;
+                if *count == 0 {
+                    in_flight.remove(&self.endpoint);
+                }
+                true
+            });
+    }
+}
+
 #[turbo_tasks::value]
+#[derive(Clone)]
 pub struct AppProject {
     project: Vc<Project>,
     app_dir: Vc<FileSystemPath>,
+    cancellation_token: Vc<CancellationToken>,
+    metadata_generators: Vec<Vc<Box<dyn MetadataRouteGenerator>>>,
+    /// Overrides the module the client main module is resolved from.
+    client_runtime_specifier: Option<RcStr>,
+    asset_url_signer: Option<Vc<Box<dyn AssetUrlSigner>>>,
+    middleware_regex_strategy: Option<Vc<Box<dyn MiddlewareRegexStrategy>>>,
+    asset_path_transform: Option<Vc<Box<dyn AssetPathTransform>>>,
+    metadata_content_type_resolver: Option<Vc<Box<dyn MetadataContentTypeResolver>>>,
+    output_asset_post_processors: Vec<Vc<Box<dyn OutputAssetPostProcessor>>>,
+    /// Only metadata routes are returned by [AppProject::routes].
+    metadata_routes_only: bool,
+    loader_tree_transform: Option<Vc<Box<dyn LoaderTreeTransform>>>,
+    server_entry_wrapper: Option<RcStr>,
+    /// Appended to the server entry chunk and manifest directory of each
+    /// endpoint, so that variants of the same routes from different app
+    /// directories don't overwrite each other.
+    variant: Option<RcStr>,
+    /// Whether the [AppProject::variant] is also appended to the page names
+    /// used as keys in the manifests.
+    variant_page_names: bool,
 }
 
+/// A metadata route that is not backed by a file in the app directory.
+#[derive(TraceRawVcs, Serialize, Deserialize, PartialEq, Eq, ValueDebugFormat, Clone, Debug)]
+pub struct SyntheticMetadataRoute {
+    /// The page name of the route without the trailing `/route` segment, e.g.
+    /// `/sitemap` or `/blog/sitemap`.
+    pub original_name: RcStr,
+    /// The module exporting the metadata handler. It is compiled like a
+    /// dynamic metadata file (e.g. `sitemap.ts`) and can live on any
+    /// filesystem.
+    pub source: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct SyntheticMetadataRoutes(Vec<SyntheticMetadataRoute>);
+
+/// Generates the regex of the middleware matcher for edge endpoints. Defaults
+/// to [get_named_middleware_regex].
+#[turbo_tasks::value_trait]
+pub trait MiddlewareRegexStrategy {
+    fn regex(self: Vc<Self>, pathname: RcStr) -> Vc<RcStr>;
+}
+
+/// A [MiddlewareRegexStrategy] for routers that don't support named groups.
+#[turbo_tasks::value]
+pub struct PositionalMiddlewareRegex;
+
+#[turbo_tasks::value_impl]
+impl PositionalMiddlewareRegex {
+    #[turbo_tasks::function]
+    pub fn new() -> Vc<Self> {
+        PositionalMiddlewareRegex.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl MiddlewareRegexStrategy for PositionalMiddlewareRegex {
+    #[turbo_tasks::function]
+    fn regex(&self, pathname: RcStr) -> Vc<RcStr> {
+        Vc::cell(get_route_regex(&pathname).regex.into())
+    }
+}
+
+/// Signs the URLs of client assets, e.g. with an expiry for preview
+/// deployments.
+#[turbo_tasks::value_trait]
+pub trait AssetUrlSigner {
+    /// Returns the signed URL for the client asset at `path`, which is
+    /// relative to the client output root.
+    fn sign(self: Vc<Self>, path: RcStr) -> Vc<RcStr>;
+}
+
+/// Transforms the loader tree of every app page before its entry is created,
+/// e.g. to wrap all pages in an additional layout or error boundary.
+#[turbo_tasks::value_trait]
+pub trait LoaderTreeTransform {
+    fn transform(self: Vc<Self>, loader_tree: Vc<LoaderTree>) -> Vc<LoaderTree>;
+}
+
+/// Provides additional metadata routes for an [AppProject], e.g. a sitemap
+/// generated from an external data source.
+#[turbo_tasks::value_trait]
+pub trait MetadataRouteGenerator {
+    fn metadata_routes(self: Vc<Self>) -> Vc<SyntheticMetadataRoutes>;
+}
+
+/// The name of the edge function created by
+/// [AppProject::combined_edge_function].
+pub const COMBINED_EDGE_FUNCTION_NAME: &str = "app-edge";
+
+/// The specifier a server entry wrapper imports the original server entry
+/// from. See [AppProject::with_server_entry_wrapper].
+pub const SERVER_ENTRY_WRAPPER_INNER: &str = "NEXT_SERVER_ENTRY";
+
 #[turbo_tasks::value(transparent)]
 pub struct OptionAppProject(Option<Vc<AppProject>>);
 
@@ -114,6 +348,16 @@ impl AppProject {
             app_dir: self.app_dir(),
         }
     }
+
+    /// Aborts all endpoint builds of the project that are still in flight.
+    /// See [CancellationToken].
+    pub async fn cancel_pending_builds(self: Vc<Self>) -> Result<()> {
+        // Updating the state of the token has to happen within a task.
+        run_once(turbo_tasks(), async move {
+            self.await?.cancellation_token.await?.cancel().await
+        })
+        .await
+    }
 }
 
 pub(crate) const ECMASCRIPT_CLIENT_TRANSITION_NAME: &str = "next-ecmascript-client-reference";
@@ -122,7 +366,162 @@ pub(crate) const ECMASCRIPT_CLIENT_TRANSITION_NAME: &str = "next-ecmascript-clie
 impl AppProject {
     #[turbo_tasks::function]
     pub fn new(project: Vc<Project>, app_dir: Vc<FileSystemPath>) -> Vc<Self> {
-        AppProject { project, app_dir }.cell()
+        AppProject {
+            project,
+            app_dir,
+            cancellation_token: CancellationToken::new(),
+            metadata_generators: vec![],
+            client_runtime_specifier: None,
+            asset_url_signer: None,
+            middleware_regex_strategy: None,
+            asset_path_transform: None,
+            metadata_content_type_resolver: None,
+            output_asset_post_processors: vec![],
+            metadata_routes_only: false,
+            loader_tree_transform: None,
+            server_entry_wrapper: None,
+            variant: None,
+            variant_page_names: false,
+        }
+        .cell()
+    }
+
+    /// Registers a generator whose metadata routes are served alongside the
+    /// file-based metadata routes of the app directory.
+    #[turbo_tasks::function]
+    pub async fn with_metadata_generator(
+        self: Vc<Self>,
+        generator: Vc<Box<dyn MetadataRouteGenerator>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.metadata_generators.push(generator);
+        Ok(this.cell())
+    }
+
+    /// Wraps the server entry of every Node.js endpoint in the module that
+    /// `specifier` resolves to from the project directory, e.g. for APM
+    /// instrumentation. The wrapper imports the original entry from
+    /// [SERVER_ENTRY_WRAPPER_INNER] and must re-export it.
+    #[turbo_tasks::function]
+    pub async fn with_server_entry_wrapper(self: Vc<Self>, specifier: RcStr) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.server_entry_wrapper = Some(specifier);
+        Ok(this.cell())
+    }
+
+    /// Applies `transform` to the loader tree of every page before its entry
+    /// is created.
+    #[turbo_tasks::function]
+    pub async fn with_loader_tree_transform(
+        self: Vc<Self>,
+        transform: Vc<Box<dyn LoaderTreeTransform>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.loader_tree_transform = Some(transform);
+        Ok(this.cell())
+    }
+
+    /// Returns a project whose endpoints write their server entry chunk and
+    /// manifests to paths suffixed with `variant`, e.g. `app/blog.b.js` for
+    /// the variant `.b`. With `suffix_page_names`, the page names in the
+    /// manifests are suffixed as well.
+    #[turbo_tasks::function]
+    pub async fn with_variant(
+        self: Vc<Self>,
+        variant: RcStr,
+        suffix_page_names: bool,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.variant = Some(variant);
+        this.variant_page_names = suffix_page_names;
+        Ok(this.cell())
+    }
+
+    /// Returns a project whose [AppProject::routes] only include metadata
+    /// routes, e.g. favicons and Open Graph images. Pages and route handlers
+    /// are skipped without creating their endpoints.
+    #[turbo_tasks::function]
+    pub async fn metadata_routes_only(self: Vc<Self>) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.metadata_routes_only = true;
+        Ok(this.cell())
+    }
+
+    /// Registers a post-processor that may replace the content of the client
+    /// and server assets of endpoints before they are written to disk.
+    /// Post-processors run in registration order.
+    #[turbo_tasks::function]
+    pub async fn with_output_asset_post_processor(
+        self: Vc<Self>,
+        processor: Vc<Box<dyn OutputAssetPostProcessor>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.output_asset_post_processors.push(processor);
+        Ok(this.cell())
+    }
+
+    /// Uses `strategy` instead of [get_named_middleware_regex] for the
+    /// middleware matchers of edge endpoints.
+    #[turbo_tasks::function]
+    pub async fn with_middleware_regex_strategy(
+        self: Vc<Self>,
+        strategy: Vc<Box<dyn MiddlewareRegexStrategy>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.middleware_regex_strategy = Some(strategy);
+        Ok(this.cell())
+    }
+
+    #[turbo_tasks::function]
+    async fn middleware_regex(self: Vc<Self>, pathname: RcStr) -> Result<Vc<RcStr>> {
+        Ok(match self.await?.middleware_regex_strategy {
+            Some(strategy) => strategy.regex(pathname),
+            None => Vc::cell(get_named_middleware_regex(&pathname).into()),
+        })
+    }
+
+    /// Registers the signer used by [AppProject::preview_manifest].
+    #[turbo_tasks::function]
+    pub async fn with_asset_url_signer(
+        self: Vc<Self>,
+        signer: Vc<Box<dyn AssetUrlSigner>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.asset_url_signer = Some(signer);
+        Ok(this.cell())
+    }
+
+    /// Rewrites the client asset paths in the build, app build and client
+    /// reference manifests with `transform`.
+    #[turbo_tasks::function]
+    pub async fn with_asset_path_transform(
+        self: Vc<Self>,
+        transform: Vc<Box<dyn AssetPathTransform>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.asset_path_transform = Some(transform);
+        Ok(this.cell())
+    }
+
+    /// Uses `resolver` for the content type of metadata routes that don't
+    /// follow a built-in metadata file convention.
+    #[turbo_tasks::function]
+    pub async fn with_metadata_content_type_resolver(
+        self: Vc<Self>,
+        resolver: Vc<Box<dyn MetadataContentTypeResolver>>,
+    ) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.metadata_content_type_resolver = Some(resolver);
+        Ok(this.cell())
+    }
+
+    /// Resolves the client main module from `specifier` instead of the default
+    /// Next.js client runtime.
+    #[turbo_tasks::function]
+    pub async fn with_client_runtime(self: Vc<Self>, specifier: RcStr) -> Result<Vc<Self>> {
+        let mut this = self.await?.clone_value();
+        this.client_runtime_specifier = Some(specifier);
+        Ok(this.cell())
     }
 
     #[turbo_tasks::function]
@@ -137,7 +536,12 @@ impl AppProject {
 
     #[turbo_tasks::function]
     fn app_entrypoints(&self) -> Vc<AppEntrypoints> {
-        get_entrypoints(self.app_dir, self.project.next_config().page_extensions())
+        let next_config = self.project.next_config();
+        get_entrypoints(
+            self.app_dir,
+            next_config.page_extensions(),
+            next_config.metadata_conflict_policy(),
+        )
     }
 
     #[turbo_tasks::function]
@@ -532,6 +936,91 @@ impl AppProject {
             .resolve_entries(Vc::upcast(self.edge_rsc_module_context()))
     }
 
+    /// Wraps `entry` in the module set with
+    /// [AppProject::with_server_entry_wrapper], if any.
+    #[turbo_tasks::function]
+    async fn wrap_server_entry(
+        self: Vc<Self>,
+        entry: Vc<Box<dyn Module>>,
+    ) -> Result<Vc<Box<dyn Module>>> {
+        let Some(specifier) = self.await?.server_entry_wrapper.clone() else {
+            return Ok(entry);
+        };
+        let project_path = self.project().project_path();
+        let module_context = self.rsc_module_context();
+        let reference_type = ReferenceType::Entry(EntryReferenceSubType::Undefined);
+        let source = resolve(
+            project_path,
+            Value::new(reference_type.clone()),
+            Request::parse(Value::new(Pattern::Constant(specifier.clone()))),
+            module_context.resolve_options(project_path, Value::new(reference_type)),
+        )
+        .first_source()
+        .await?
+        .with_context(|| {
+            format!("expected server entry wrapper \"{specifier}\" to resolve to a file")
+        })?;
+        Ok(module_context
+            .process(
+                source,
+                Value::new(ReferenceType::Internal(Vc::cell(indexmap! {
+                    SERVER_ENTRY_WRAPPER_INNER.into() => entry,
+                }))),
+            )
+            .module())
+    }
+
+    /// The configured `serverEntryModules`, which are evaluated by the server
+    /// entry chunk of every Node.js endpoint.
+    #[turbo_tasks::function]
+    async fn server_entry_modules(self: Vc<Self>) -> Result<Vc<EvaluatableAssets>> {
+        let project_path = self.project().project_path();
+        let entries = self
+            .project()
+            .next_config()
+            .server_entry_modules()
+            .await?
+            .iter()
+            .map(|specifier| {
+                RuntimeEntry::Request(
+                    Request::parse(Value::new(Pattern::Constant(specifier.clone()))),
+                    project_path.join("_".into()),
+                )
+                .cell()
+            })
+            .collect();
+        Ok(Vc::<RuntimeEntries>::cell(entries)
+            .resolve_entries(Vc::upcast(self.rsc_module_context())))
+    }
+
+    /// The chunks of the server runtime entries, shared by all Node.js
+    /// endpoints of the app.
+    #[turbo_tasks::function]
+    async fn server_shared_chunk_group(
+        self: Vc<Self>,
+        process_client: bool,
+    ) -> Result<Vc<ChunkGroupResult>> {
+        let runtime_entries: Vec<Vc<Box<dyn Module>>> = self
+            .rsc_runtime_entries()
+            .await?
+            .iter()
+            .map(|&entry| Vc::upcast(entry))
+            .collect();
+        let shared_module = IncludeModulesModule::new(
+            AssetIdent::from_path(self.project().project_path())
+                .with_modifier(server_shared_chunks()),
+            runtime_entries,
+        );
+        Ok(self
+            .project()
+            .server_chunking_context(process_client)
+            .chunk_group(
+                shared_module.ident(),
+                Vc::upcast(shared_module),
+                Value::new(AvailabilityInfo::Root),
+            ))
+    }
+
     #[turbo_tasks::function]
     fn client_env(self: Vc<Self>) -> Vc<Box<dyn ProcessEnv>> {
         Vc::upcast(CustomProcessEnv::new(
@@ -552,13 +1041,104 @@ impl AppProject {
         .resolve_entries(Vc::upcast(self.client_module_context())))
     }
 
+    /// Entrypoints for the metadata routes of all registered
+    /// [MetadataRouteGenerator]s.
+    #[turbo_tasks::function]
+    async fn synthetic_metadata_entrypoints(self: Vc<Self>) -> Result<Vc<AppEntrypoints>> {
+        let mut entrypoints = IndexMap::new();
+        for generator in self.await?.metadata_generators.iter() {
+            for route in generator.metadata_routes().await?.iter() {
+                let page = normalize_metadata_route(AppPage::parse(&route.original_name)?)?;
+                let pathname = AppPath::from(page.clone());
+                if entrypoints.contains_key(&pathname) {
+                    bail!(
+                        "metadata route {} is provided by more than one generator",
+                        route.original_name
+                    );
+                }
+                entrypoints.insert(
+                    pathname,
+                    AppEntrypoint::AppMetadata {
+                        page,
+                        metadata: MetadataItem::Dynamic { path: route.source },
+                    },
+                );
+            }
+        }
+        Ok(Vc::cell(entrypoints))
+    }
+
+    /// Warns about robots and sitemap files that define the same metadata
+    /// route, e.g. a `robots.txt` next to a `robots.ts`. Only one of them is
+    /// used for the route.
+    #[turbo_tasks::function]
+    async fn check_metadata_routes(self: Vc<Self>) -> Result<Vc<Completion>> {
+        let page_extensions = self.project().next_config().page_extensions().await?;
+        for entrypoint in self.app_entrypoints().await?.values() {
+            let AppEntrypoint::AppMetadata { page, metadata } = entrypoint else {
+                continue;
+            };
+            let path = metadata.into_path();
+            let path_value = path.await?;
+            let file_name = path_value.file_name();
+            let Some(metadata_type) = robots_or_sitemap_metadata_type(file_name, &page_extensions)
+            else {
+                continue;
+            };
+            let DirectoryContent::Entries(entries) = &*path.parent().read_dir().await? else {
+                continue;
+            };
+            for (basename, entry) in entries {
+                if !matches!(entry, DirectoryEntry::File(_))
+                    || basename.as_str() == file_name
+                    || robots_or_sitemap_metadata_type(basename, &page_extensions)
+                        != Some(metadata_type)
+                {
+                    continue;
+                }
+                ConflictIssue {
+                    path,
+                    title: StyledString::Text(
+                        format!(
+                            "{file_name} and {basename} both define the {metadata_type} of {}",
+                            AppPath::from(page.clone())
+                        )
+                        .into(),
+                    )
+                    .cell(),
+                    description: StyledString::Text(
+                        "Only one of them is used. Please remove the other file.".into(),
+                    )
+                    .cell(),
+                    severity: IssueSeverity::Warning.cell(),
+                }
+                .cell()
+                .emit();
+            }
+        }
+        Ok(Completion::new())
+    }
+
     #[turbo_tasks::function]
     pub async fn routes(self: Vc<Self>) -> Result<Vc<Routes>> {
-        let app_entrypoints = self.app_entrypoints();
+        self.check_metadata_routes().await?;
+        let app_entrypoints = self.app_entrypoints().await?;
+        let synthetic_entrypoints = self.synthetic_metadata_entrypoints().await?;
+        if let Some(pathname) = synthetic_entrypoints
+            .keys()
+            .find(|pathname| app_entrypoints.contains_key(*pathname))
+        {
+            bail!("metadata route {pathname} conflicts with a file in the app directory");
+        }
+        let metadata_routes_only = self.await?.metadata_routes_only;
         Ok(Vc::cell(
             app_entrypoints
-                .await?
                 .iter()
+                .chain(synthetic_entrypoints.iter())
+                .filter(|(_, app_entrypoint)| {
+                    !metadata_routes_only
+                        || matches!(app_entrypoint, AppEntrypoint::AppMetadata { .. })
+                })
                 .map(|(pathname, app_entrypoint)| async {
                     Ok((
                         pathname.to_string().into(),
@@ -574,107 +1154,989 @@ impl AppProject {
         ))
     }
 
+    /// Counts the endpoints [AppProject::routes] returns without creating or
+    /// building any of them, e.g. as the total for progress reporting.
+    ///
+    /// Every page has an HTML and an RSC endpoint, unless only RSC payloads
+    /// are built, while route handlers and metadata routes have a single
+    /// endpoint.
+    #[turbo_tasks::function]
+    pub async fn entrypoint_count(self: Vc<Self>) -> Result<Vc<usize>> {
+        let endpoints_per_page = if *self.project().next_config().rsc_only().await? {
+            1
+        } else {
+            2
+        };
+        let metadata_routes_only = self.await?.metadata_routes_only;
+        let app_entrypoints = self.app_entrypoints().await?;
+        let synthetic_entrypoints = self.synthetic_metadata_entrypoints().await?;
+        let count = app_entrypoints
+            .values()
+            .chain(synthetic_entrypoints.values())
+            .map(|entrypoint| match entrypoint {
+                AppEntrypoint::AppPage { .. } | AppEntrypoint::AppRoute { .. }
+                    if metadata_routes_only =>
+                {
+                    0
+                }
+                AppEntrypoint::AppPage { pages, .. } => pages.len() * endpoints_per_page,
+                AppEntrypoint::AppRoute { .. } | AppEntrypoint::AppMetadata { .. } => 1,
+            })
+            .sum();
+        Ok(Vc::cell(count))
+    }
+
+    /// Returns the endpoint of kind `kind` of the app route with the original
+    /// name `page`, e.g. `/blog/[slug]/page`, without creating the endpoints of
+    /// other routes. It is the same endpoint that [AppProject::routes] returns,
+    /// so a single endpoint can be built from its page and kind, e.g. in a
+    /// worker process.
+    #[turbo_tasks::function]
+    pub async fn endpoint_for(
+        self: Vc<Self>,
+        page: RcStr,
+        kind: AppEndpointKind,
+    ) -> Result<Vc<Box<dyn Endpoint>>> {
+        let app_entrypoints = self.app_entrypoints().await?;
+        let synthetic_entrypoints = self.synthetic_metadata_entrypoints().await?;
+        let Some(entrypoint) = app_entrypoints
+            .values()
+            .chain(synthetic_entrypoints.values())
+            .find(|entrypoint| match entrypoint {
+                AppEntrypoint::AppPage { pages, .. } => {
+                    pages.iter().any(|app_page| app_page.to_string() == *page)
+                }
+                AppEntrypoint::AppRoute { page: app_page, .. }
+                | AppEntrypoint::AppMetadata { page: app_page, .. } => {
+                    app_page.to_string() == *page
+                }
+            })
+        else {
+            bail!("app route {page} doesn't exist");
+        };
+        if self.await?.metadata_routes_only
+            && !matches!(entrypoint, AppEntrypoint::AppMetadata { .. })
+        {
+            bail!("app route {page} isn't built, as only metadata routes are built");
+        }
+
+        let route = app_entry_point_to_route(self, entrypoint.clone()).await?;
+        Ok(match (&*route, kind) {
+            (Route::AppPage(pages), AppEndpointKind::Html | AppEndpointKind::Rsc) => {
+                let page_route = pages
+                    .iter()
+                    .find(|page_route| page_route.original_name == *page)
+                    .context("page route should exist for each page of the entrypoint")?;
+                match kind {
+                    AppEndpointKind::Html => page_route.html_endpoint,
+                    _ => page_route.rsc_endpoint,
+                }
+            }
+            (Route::AppRoute { endpoint, .. }, AppEndpointKind::Route) => *endpoint,
+            _ => bail!("app route {page} has no {kind:?} endpoint"),
+        })
+    }
+
     #[turbo_tasks::function]
     pub async fn client_main_module(self: Vc<Self>) -> Result<Vc<Box<dyn Module>>> {
         let client_module_context = Vc::upcast(self.client_module_context());
 
+        let client_runtime_specifier = self.await?.client_runtime_specifier.clone();
+        let is_custom_runtime = client_runtime_specifier.is_some();
+        let specifier = client_runtime_specifier
+            .unwrap_or_else(|| "next/dist/client/app-next-turbopack.js".into());
+
         let client_main_module = cjs_resolve(
             Vc::upcast(PlainResolveOrigin::new(
                 client_module_context,
                 self.project().project_path().join("_".into()),
             )),
-            Request::parse(Value::new(Pattern::Constant(
-                "next/dist/client/app-next-turbopack.js".into(),
-            ))),
+            Request::parse(Value::new(Pattern::Constant(specifier.clone()))),
             None,
-            IssueSeverity::Error.cell(),
+            self.project().next_config().runtime_resolve_severity(),
         )
         .resolve()
         .await?
         .first_module()
-        .await?
-        .context("expected Next.js client runtime to resolve to a module")?;
+        .await?;
+
+        let client_main_module = if is_custom_runtime {
+            client_main_module.with_context(|| {
+                format!("expected custom client runtime \"{specifier}\" to resolve to a module")
+            })?
+        } else {
+            client_main_module.context("expected Next.js client runtime to resolve to a module")?
+        };
 
         Ok(client_main_module)
     }
-}
 
-#[turbo_tasks::function]
-pub fn app_entry_point_to_route(
-    app_project: Vc<AppProject>,
-    entrypoint: AppEntrypoint,
-) -> Vc<Route> {
-    match entrypoint {
-        AppEntrypoint::AppPage { pages, loader_tree } => Route::AppPage(
-            pages
-                .into_iter()
-                .map(|page| AppPageRoute {
-                    original_name: page.to_string(),
-                    html_endpoint: Vc::upcast(
-                        AppEndpoint {
-                            ty: AppEndpointType::Page {
-                                ty: AppPageEndpointType::Html,
-                                loader_tree,
-                            },
-                            app_project,
-                            page: page.clone(),
-                        }
-                        .cell(),
-                    ),
-                    rsc_endpoint: Vc::upcast(
-                        AppEndpoint {
-                            ty: AppEndpointType::Page {
-                                ty: AppPageEndpointType::Rsc,
-                                loader_tree,
-                            },
-                            app_project,
-                            page,
-                        }
-                        .cell(),
-                    ),
-                })
-                .collect(),
-        ),
-        AppEntrypoint::AppRoute {
-            page,
-            path,
-            root_layouts,
-        } => Route::AppRoute {
-            original_name: page.to_string(),
-            endpoint: Vc::upcast(
-                AppEndpoint {
-                    ty: AppEndpointType::Route { path, root_layouts },
-                    app_project,
-                    page,
+    /// Removes files written for app routes by a previous build that don't
+    /// belong to any of the current routes.
+    ///
+    /// Only files listed in the outputs index written by a previous call are
+    /// considered, so files not managed by this build are never removed.
+    #[turbo_tasks::function]
+    pub async fn clean_stale_outputs(self: Vc<Self>) -> Result<Vc<Completion>> {
+        let node_root = self.project().node_root();
+        let index_path = node_root.join(APP_OUTPUTS_INDEX.into());
+
+        let endpoints = route_endpoints(&self.routes().await?);
+
+        // Paths are relative to the node root, as client assets are emitted there too.
+        let mut current_paths = IndexSet::new();
+        for endpoint in endpoints {
+            match &*endpoint.write_to_disk().await? {
+                WrittenEndpoint::NodeJs {
+                    server_paths,
+                    client_paths,
+                    ..
                 }
-                .cell(),
-            ),
-        },
-        AppEntrypoint::AppMetadata { page, metadata } => Route::AppRoute {
-            original_name: page.to_string(),
-            endpoint: Vc::upcast(
-                AppEndpoint {
-                    ty: AppEndpointType::Metadata { metadata },
-                    app_project,
-                    page,
+                | WrittenEndpoint::Edge {
+                    server_paths,
+                    client_paths,
+                } => {
+                    current_paths.extend(
+                        server_paths
+                            .iter()
+                            .map(|server_path| RcStr::from(server_path.path.as_str())),
+                    );
+                    current_paths.extend(client_paths.iter().cloned());
                 }
-                .cell(),
-            ),
-        },
+                // The files of a cancelled build aren't known, so nothing is removed until
+                // it's written again.
+                WrittenEndpoint::Cancelled => return Ok(Completion::new()),
+            }
+        }
+
+        let previous_paths: Vec<RcStr> = match &*index_path.read().await? {
+            FileContent::Content(file) => parse_json_rope_with_source_context(file.content())
+                .context("failed to parse the app outputs index")?,
+            FileContent::NotFound => vec![],
+        };
+
+        for path in previous_paths {
+            if !current_paths.contains(&path) {
+                node_root
+                    .join(path)
+                    .write(FileContent::NotFound.cell())
+                    .await?;
+            }
+        }
+
+        index_path
+            .write(
+                FileContent::Content(File::from(serde_json::to_string_pretty(&current_paths)?))
+                    .cell(),
+            )
+            .await?;
+
+        Ok(Completion::new())
     }
-    .cell()
-}
 
-#[turbo_tasks::function]
-fn client_shared_chunks() -> Vc<RcStr> {
-    Vc::cell("client_shared_chunks".into())
-}
+    /// Writes the endpoints of all app routes and hashes the files each route
+    /// wrote, keyed by its original name. The hash covers the path and content
+    /// hash of every server file and the path of every client file, whose names
+    /// contain their content hash in production builds.
+    #[turbo_tasks::function]
+    pub async fn route_hashes(self: Vc<Self>) -> Result<Vc<RouteHashes>> {
+        let routes = self.routes().await?;
+        let mut route_endpoints = vec![];
+        for route in routes.values() {
+            match route {
+                Route::AppPage(pages) => route_endpoints.extend(pages.iter().map(|page| {
+                    (
+                        RcStr::from(page.original_name.as_str()),
+                        vec![page.html_endpoint, page.rsc_endpoint],
+                    )
+                })),
+                Route::AppRoute {
+                    original_name,
+                    endpoint,
+                } => route_endpoints.push((RcStr::from(original_name.as_str()), vec![*endpoint])),
+                _ => {}
+            }
+        }
 
-#[derive(Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Debug, TraceRawVcs)]
-enum AppPageEndpointType {
-    Html,
-    Rsc,
-}
+        let hashes = route_endpoints
+            .into_iter()
+            .map(|(original_name, endpoints)| async move {
+                let mut hasher = Xxh3Hash64Hasher::new();
+                for endpoint in endpoints {
+                    let (WrittenEndpoint::NodeJs {
+                        server_paths,
+                        client_paths,
+                        ..
+                    }
+                    | WrittenEndpoint::Edge {
+                        server_paths,
+                        client_paths,
+                    }) = &*endpoint.write_to_disk().await?
+                    else {
+                        continue;
+                    };
+                    for server_path in server_paths {
+                        hasher.write_ref(&server_path.path);
+                        hasher.write_value(server_path.content_hash);
+                    }
+                    for client_path in client_paths {
+                        hasher.write_value(client_path.as_str());
+                    }
+                }
+                Ok((original_name, encode_hex(hasher.finish()).into()))
+            })
+            .try_join()
+            .await?;
+        Ok(Vc::cell(hashes.into_iter().collect()))
+    }
+
+    /// Compares the current app routes against the [AppProject::route_hashes]
+    /// of a previous build, e.g. to only redeploy the routes that changed.
+    #[turbo_tasks::function]
+    pub async fn diff_routes(self: Vc<Self>, previous: Vc<RouteHashes>) -> Result<Vc<RoutesDiff>> {
+        let previous = previous.await?;
+        let current = self.route_hashes().await?;
+
+        let mut diff = RoutesDiff::default();
+        for (original_name, hash) in current.iter() {
+            match previous.get(original_name) {
+                None => diff.added.push(original_name.clone()),
+                Some(previous_hash) if previous_hash != hash => {
+                    diff.changed.push(original_name.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        diff.removed = previous
+            .keys()
+            .filter(|original_name| !current.contains_key(*original_name))
+            .cloned()
+            .collect();
+        Ok(diff.cell())
+    }
+
+    /// Creates `server/preview-manifest.json`, which maps the client assets of
+    /// all app routes to URLs signed by the registered [AssetUrlSigner].
+    #[turbo_tasks::function]
+    pub async fn preview_manifest(self: Vc<Self>) -> Result<Vc<Box<dyn OutputAsset>>> {
+        let Some(signer) = self.await?.asset_url_signer else {
+            bail!("an asset URL signer must be registered to create a preview manifest");
+        };
+        let client_relative_path = self.project().client_relative_path();
+
+        let mut manifest = PreviewManifest::default();
+        for (endpoint, _) in unique_app_endpoints(&self.routes().await?).await? {
+            let client_assets = endpoint.output().client_assets();
+            for path in all_paths_in_root(client_assets, client_relative_path)
+                .await?
+                .iter()
+            {
+                if !manifest.assets.contains_key(path) {
+                    let url = signer.sign(path.clone()).await?.clone_value();
+                    manifest.assets.insert(path.clone(), url);
+                }
+            }
+        }
+
+        Ok(Vc::upcast(VirtualOutputAsset::new(
+            self.project()
+                .node_root()
+                .join("server/preview-manifest.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+        )))
+    }
+
+    /// Creates `server/chunk-routes-index.json`, which lists the original
+    /// names of the app routes loading each client and server chunk, e.g. to
+    /// find the routes to revalidate when a chunk changed.
+    #[turbo_tasks::function]
+    pub async fn chunk_routes_index(self: Vc<Self>) -> Result<Vc<Box<dyn OutputAsset>>> {
+        let node_root = self.project().node_root();
+        let node_root_ref = node_root.await?;
+        let client_relative_path = self.project().client_relative_path();
+
+        let mut index = ChunkRoutesIndex::default();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            let output = endpoint.output();
+            let client_paths = all_paths_in_root(output.client_assets(), client_relative_path);
+            // Server assets include the manifests of the route, which aren't chunks.
+            let server_assets = all_assets_from_entries(output.server_assets()).await?;
+            let server_paths = get_paths_from_root(&node_root_ref, &server_assets, |path| {
+                path.ends_with(".js") && !path.ends_with("-manifest.js")
+            })
+            .await?;
+            for path in client_paths.await?.iter().chain(server_paths.iter()) {
+                index
+                    .chunks
+                    .entry(path.clone())
+                    .or_default()
+                    .push(app_entry.original_name.clone());
+            }
+        }
+
+        Ok(Vc::upcast(VirtualOutputAsset::new(
+            node_root.join("server/chunk-routes-index.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&index)?).into()),
+        )))
+    }
+
+    /// Creates `server/server-actions-manifest.json`, which lists the id of
+    /// every server action of the app routes with the routes that can invoke
+    /// it. An action that is reachable from multiple routes lists all of them.
+    #[turbo_tasks::function]
+    pub async fn server_actions_manifest(self: Vc<Self>) -> Result<Vc<Box<dyn OutputAsset>>> {
+        let mut manifest = ServerActionsRoutesManifest::default();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            for id in endpoint.output().server_action_ids().await?.iter() {
+                manifest
+                    .actions
+                    .entry(id.clone())
+                    .or_insert_with(|| ServerActionRoutes {
+                        method: "POST".into(),
+                        routes: vec![],
+                    })
+                    .routes
+                    .push(app_entry.original_name.clone());
+            }
+        }
+        manifest.actions.sort_keys();
+
+        Ok(Vc::upcast(VirtualOutputAsset::new(
+            self.project()
+                .node_root()
+                .join("server/server-actions-manifest.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+        )))
+    }
+
+    /// Returns the names and matchers of all app routes that use the edge
+    /// runtime, sorted by name. Only the entries of the routes are created,
+    /// their endpoints aren't built.
+    #[turbo_tasks::function]
+    pub async fn edge_functions(self: Vc<Self>) -> Result<Vc<EdgeFunctionMatchers>> {
+        let mut functions = BTreeMap::new();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            if !matches!(app_entry.config.await?.runtime, Some(NextRuntime::Edge)) {
+                continue;
+            }
+            let matcher = endpoint.edge_function_matcher().await?;
+            functions.insert(matcher.name.clone(), matcher.clone_value());
+        }
+        Ok(Vc::cell(functions.into_values().collect()))
+    }
+
+    /// Returns the runtime and type of every app route, keyed by its original
+    /// name. Only the route entries are created, their endpoints aren't built.
+    #[turbo_tasks::function]
+    pub async fn route_runtimes(self: Vc<Self>) -> Result<Vc<AppRouteRuntimes>> {
+        let mut runtimes = BTreeMap::new();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            let ty = match endpoint.await?.ty {
+                AppEndpointType::Page { .. } => AppRouteType::Page,
+                AppEndpointType::Route { .. } => AppRouteType::Route,
+                AppEndpointType::Metadata { .. } => AppRouteType::Metadata,
+            };
+            runtimes.insert(
+                app_entry.original_name.clone(),
+                AppRouteRuntime {
+                    runtime: app_entry.config.await?.runtime.unwrap_or_default(),
+                    ty,
+                },
+            );
+        }
+        Ok(Vc::cell(runtimes))
+    }
+
+    /// Creates `federation-manifest.json`, which lists the modules configured
+    /// in `experimental.turbo.federationExposes` with the client chunks that
+    /// load them, and returns it together with those chunks. Returns no assets
+    /// when no modules are exposed.
+    #[turbo_tasks::function]
+    pub async fn federation_manifest(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let exposes = self.project().next_config().federation_exposes().await?;
+        if exposes.is_empty() {
+            return Ok(OutputAssets::empty());
+        }
+
+        let project_path = self.project().project_path();
+        let client_chunking_context = self.project().client_chunking_context();
+        let client_relative_path_ref = self.project().client_relative_path().await?;
+
+        let mut assets = vec![];
+        let mut manifest = FederationManifest::default();
+        for (name, module_path) in exposes.iter() {
+            let module = self
+                .client_module_context()
+                .process(
+                    Vc::upcast(FileSource::new(project_path.join(module_path.clone()))),
+                    Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined)),
+                )
+                .module();
+            let Some(module) = Vc::try_resolve_sidecast::<Box<dyn ChunkableModule>>(module).await?
+            else {
+                bail!("exposed module {name} ({module_path}) can't be chunked");
+            };
+            let chunks = client_chunking_context
+                .chunk_group_assets(module, Value::new(AvailabilityInfo::Root))
+                .await?;
+            let mut chunk_paths = vec![];
+            for &chunk in chunks.iter() {
+                let path = chunk.ident().path().await?;
+                let path = client_relative_path_ref
+                    .get_path_to(&path)
+                    .with_context(|| {
+                        format!(
+                            "chunk {} of exposed module {name} is expected to be inside {}",
+                            path.path, client_relative_path_ref.path
+                        )
+                    })?;
+                chunk_paths.push(path.into());
+                assets.push(chunk);
+            }
+            manifest.exposes.insert(
+                name.clone(),
+                FederationExposedModule {
+                    module: module_path.clone(),
+                    chunks: chunk_paths,
+                },
+            );
+        }
+
+        assets.push(Vc::upcast(VirtualOutputAsset::new(
+            self.project()
+                .node_root()
+                .join("federation-manifest.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+        )));
+        Ok(Vc::cell(assets))
+    }
+
+    /// Combines all app routes that use the edge runtime into a single edge
+    /// function named [COMBINED_EDGE_FUNCTION_NAME], for platforms that limit
+    /// the number of functions per deployment.
+    ///
+    /// Creates `server/combined-middleware-manifest.json`, whose function
+    /// loads the files and has the matchers of all edge routes, and
+    /// `server/app-edge-router.js`, which dispatches each request to the first
+    /// route whose matcher matches its pathname. The endpoints still emit their
+    /// own edge functions.
+    #[turbo_tasks::function]
+    pub async fn combined_edge_function(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let mut endpoints = BTreeMap::new();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            if matches!(app_entry.config.await?.runtime, Some(NextRuntime::Edge)) {
+                endpoints.insert(app_entry.pathname.clone(), endpoint);
+            }
+        }
+
+        let node_root = self.project().node_root();
+        let router_path: RcStr = "server/app-edge-router.js".into();
+        let mut files = IndexSet::new();
+        let mut wasm = IndexSet::new();
+        let mut assets = IndexSet::new();
+        let mut matchers = vec![];
+        let mut routes = vec![];
+        for endpoint in endpoints.into_values() {
+            let AppEndpointOutput::Edge {
+                edge_function_files,
+                ..
+            } = *endpoint.output().await?
+            else {
+                continue;
+            };
+            let edge_function_files = edge_function_files.await?;
+            files.extend(edge_function_files.files.iter().cloned());
+            wasm.extend(edge_function_files.wasm.iter().cloned());
+            assets.extend(edge_function_files.assets.iter().cloned());
+
+            let matcher = endpoint.edge_function_matcher().await?;
+            matchers.push(MiddlewareMatcher {
+                regexp: Some(matcher.regex.clone()),
+                original_source: matcher.original_source.clone(),
+                ..Default::default()
+            });
+            routes.push((matcher.regex.clone(), matcher.name.clone()));
+        }
+        files.insert(router_path.clone());
+
+        let router = combined_edge_router(&routes)?;
+
+        let name: RcStr = COMBINED_EDGE_FUNCTION_NAME.into();
+        let edge_function_definition = EdgeFunctionDefinition {
+            files: files.into_iter().collect(),
+            wasm: wasm_paths_to_bindings(wasm.into_iter().collect()),
+            assets: paths_to_bindings(assets.into_iter().collect()),
+            name: name.clone(),
+            page: format!("/{COMBINED_EDGE_FUNCTION_NAME}").into(),
+            regions: None,
+            matchers,
+            env: self.project().edge_env().await?.clone_value(),
+        };
+        let middleware_manifest_v2 = MiddlewaresManifestV2 {
+            sorted_middleware: vec![name.clone()],
+            functions: [(name, edge_function_definition)].into_iter().collect(),
+            ..Default::default()
+        };
+
+        Ok(Vc::cell(vec![
+            Vc::upcast(VirtualOutputAsset::new(
+                node_root.join("server/combined-middleware-manifest.json".into()),
+                AssetContent::file(
+                    File::from(serde_json::to_string_pretty(&middleware_manifest_v2)?).into(),
+                ),
+            )),
+            Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(router_path),
+                AssetContent::file(File::from(router).into()),
+            )),
+        ]))
+    }
+
+    /// Creates `prerender-manifest.json`, which lists the prerender config of
+    /// all app routes that set `revalidate` or opt into static rendering.
+    /// Routes with `dynamic = "force-dynamic"` are never prerendered.
+    #[turbo_tasks::function]
+    pub async fn prerender_manifest(self: Vc<Self>) -> Result<Vc<Box<dyn OutputAsset>>> {
+        let mut manifest = PrerenderManifest::default();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            let pathname = &app_entry.pathname;
+            let config = app_entry.config.await?;
+            let prerender = match config.dynamic {
+                Some(NextSegmentDynamic::ForceDynamic) => false,
+                Some(NextSegmentDynamic::ForceStatic | NextSegmentDynamic::Error) => true,
+                Some(NextSegmentDynamic::Auto) | None => config.revalidate.is_some(),
+            };
+            if !prerender {
+                continue;
+            }
+
+            let data_route: RcStr = format!(
+                "{}.rsc",
+                if pathname == "/" {
+                    "/index"
+                } else {
+                    pathname.as_str()
+                }
+            )
+            .into();
+            if AppPath::from(endpoint.await?.page.clone()).is_dynamic() {
+                manifest.dynamic_routes.insert(
+                    pathname.clone(),
+                    DynamicPrerenderRoute {
+                        route_regex: get_route_regex(pathname).regex.into(),
+                        data_route,
+                        fallback: (config.dynamic_params == Some(false)).then_some(false),
+                    },
+                );
+            } else {
+                manifest.routes.insert(
+                    pathname.clone(),
+                    PrerenderRoute {
+                        initial_revalidate_seconds: config.revalidate.unwrap_or_default().into(),
+                        src_route: pathname.clone(),
+                        data_route,
+                    },
+                );
+            }
+        }
+
+        Ok(Vc::upcast(VirtualOutputAsset::new(
+            self.project()
+                .node_root()
+                .join("prerender-manifest.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+        )))
+    }
+
+    /// Creates `static-routes-manifest.json`, which lists the app routes that
+    /// are fully static, so they can be deployed separately from dynamic
+    /// routes. A route is static when its resolved segment config allows
+    /// prerendering, see [NextSegmentConfig::allows_prerender], and it has no
+    /// dynamic segments.
+    ///
+    /// Routes with dynamic segments are never listed, even when they export
+    /// `generateStaticParams`, as their pathnames are only known once it runs.
+    /// `prerender-manifest.json` lists them as dynamic routes instead.
+    #[turbo_tasks::function]
+    pub async fn static_routes_manifest(self: Vc<Self>) -> Result<Vc<Box<dyn OutputAsset>>> {
+        let mut manifest = StaticRoutesManifest::default();
+        for (endpoint, app_entry) in unique_app_endpoints(&self.routes().await?).await? {
+            if AppPath::from(endpoint.await?.page.clone()).is_dynamic()
+                || !endpoint.resolved_segment_config().await?.allows_prerender()
+            {
+                continue;
+            }
+            manifest.routes.insert(app_entry.pathname.clone());
+        }
+
+        Ok(Vc::upcast(VirtualOutputAsset::new(
+            self.project()
+                .node_root()
+                .join("static-routes-manifest.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+        )))
+    }
+
+    /// Creates `server/wasm-inventory.json`, which lists the WASM assets of
+    /// all app routes together with their binding names.
+    #[turbo_tasks::function]
+    pub async fn wasm_inventory(self: Vc<Self>) -> Result<Vc<Box<dyn OutputAsset>>> {
+        let node_root = self.project().node_root();
+        let node_root_value = node_root.await?;
+
+        let mut wasm_paths = IndexSet::new();
+        for (endpoint, _) in unique_app_endpoints(&self.routes().await?).await? {
+            let all_output_assets =
+                all_assets_from_entries(endpoint.output().output_assets()).await?;
+            wasm_paths
+                .extend(get_wasm_paths_from_root(&node_root_value, &all_output_assets).await?);
+        }
+
+        let manifest = WasmInventoryManifest {
+            wasm: wasm_paths_to_bindings(wasm_paths.into_iter().collect()),
+        };
+
+        Ok(Vc::upcast(VirtualOutputAsset::new(
+            node_root.join("server/wasm-inventory.json".into()),
+            AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+        )))
+    }
+
+    /// Computes the module and resolve options contexts shared by all
+    /// endpoints in parallel, so the first endpoint doesn't have to compute
+    /// them one after another.
+    #[turbo_tasks::function]
+    pub async fn warmup(self: Vc<Self>) -> Result<Vc<Completion>> {
+        [
+            self.rsc_module_options_context(),
+            self.edge_rsc_module_options_context(),
+            self.route_module_options_context(),
+            self.edge_route_module_options_context(),
+            self.client_module_options_context(),
+        ]
+        .into_iter()
+        .map(|context| async move { context.await })
+        .try_join()
+        .await?;
+
+        [
+            self.rsc_resolve_options_context(),
+            self.edge_rsc_resolve_options_context(),
+            self.route_resolve_options_context(),
+            self.edge_route_resolve_options_context(),
+            self.client_resolve_options_context(),
+        ]
+        .into_iter()
+        .map(|context| async move { context.await })
+        .try_join()
+        .await?;
+
+        [
+            self.rsc_module_context(),
+            self.edge_rsc_module_context(),
+            self.route_module_context(),
+            self.edge_route_module_context(),
+            self.client_module_context(),
+        ]
+        .into_iter()
+        .map(|context| context.resolve())
+        .try_join()
+        .await?;
+
+        Ok(Completion::new())
+    }
+}
+
+/// Creates the code of the router of the [AppProject::combined_edge_function],
+/// which dispatches each request to the edge function of the first of `routes`
+/// whose regex matches its pathname. `routes` are pairs of the regex and name
+/// of each edge function.
+fn combined_edge_router(routes: &[(RcStr, RcStr)]) -> Result<String> {
+    // The regexes use Rust's `(?P<name>...)` syntax for named groups, which
+    // JavaScript doesn't support.
+    let routes = routes
+        .iter()
+        .map(|(regex, name)| (regex.replace("(?P<", "(?<"), name))
+        .collect::<Vec<_>>();
+    Ok(format!(
+        r#"self._ENTRIES ||= {{}};
+const routes = {routes}.map(([regex, name]) => [new RegExp(regex), name]);
+self._ENTRIES[{entry}] = Promise.resolve({{
+  default(args) {{
+    const {{ pathname }} = new URL(args.request.url);
+    const route = routes.find(([regex]) => regex.test(pathname));
+    if (!route) {{
+      throw new Error(`No edge route matches ${{pathname}}`);
+    }}
+    return self._ENTRIES[`middleware_${{route[1]}}`].then((entry) => entry.default(args));
+  }},
+}});
+"#,
+        routes = serde_json::to_string(&routes)?,
+        entry = serde_json::to_string(&format!("middleware_{COMBINED_EDGE_FUNCTION_NAME}"))?,
+    ))
+}
+
+/// Returns all endpoints of the app routes in `routes`.
+fn route_endpoints(routes: &IndexMap<RcStr, Route>) -> Vec<Vc<Box<dyn Endpoint>>> {
+    routes
+        .values()
+        .flat_map(|route| match route {
+            Route::AppPage(pages) => pages
+                .iter()
+                .flat_map(|page| [page.html_endpoint, page.rsc_endpoint])
+                .collect(),
+            Route::AppRoute { endpoint, .. } => vec![*endpoint],
+            _ => vec![],
+        })
+        .collect()
+}
+
+/// Returns one [AppEndpoint] for each pathname of the app routes in `routes`,
+/// together with its entry, in the order of the routes. The HTML and RSC
+/// endpoints of a page share the same entry, so only the first one is
+/// returned.
+async fn unique_app_endpoints(
+    routes: &IndexMap<RcStr, Route>,
+) -> Result<Vec<(Vc<AppEndpoint>, ReadRef<AppEntry>)>> {
+    let mut pathnames = HashSet::new();
+    let mut endpoints = vec![];
+    for endpoint in route_endpoints(routes) {
+        let Some(endpoint) = Vc::try_resolve_downcast_type::<AppEndpoint>(endpoint).await? else {
+            continue;
+        };
+        let app_entry = endpoint.app_endpoint_entry().await?;
+        if pathnames.insert(app_entry.pathname.clone()) {
+            endpoints.push((endpoint, app_entry));
+        }
+    }
+    Ok(endpoints)
+}
+
+/// Records how much the memory usage of the process grew during each phase of
+/// building an endpoint.
+///
+/// Other tasks run concurrently and results can be shared between endpoints,
+/// so the numbers are only an approximation.
+struct MemoryReport {
+    enabled: bool,
+    last_usage: usize,
+    phases: Vec<(&'static str, isize)>,
+}
+
+impl MemoryReport {
+    fn new(enabled: bool) -> Self {
+        MemoryReport {
+            enabled,
+            last_usage: if enabled {
+                TurboMalloc::memory_usage()
+            } else {
+                0
+            },
+            phases: vec![],
+        }
+    }
+
+    /// Attributes the memory allocated since the last phase to `phase`.
+    fn record(&mut self, phase: &'static str) {
+        if !self.enabled {
+            return;
+        }
+        let usage = TurboMalloc::memory_usage();
+        self.phases
+            .push((phase, usage as isize - self.last_usage as isize));
+        self.last_usage = usage;
+    }
+
+    fn log(&self, route: &str) {
+        if !self.enabled {
+            return;
+        }
+        let summary = self
+            .phases
+            .iter()
+            .map(|&(phase, delta)| {
+                let sign = if delta < 0 { "-" } else { "+" };
+                format!("{phase}: {sign}{}", FormatBytes(delta.unsigned_abs()))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        tracing::info!("memory usage of {route}: {summary}");
+    }
+}
+
+/// The file in the node root listing all files written for app routes.
+const APP_OUTPUTS_INDEX: &str = "server/app-outputs-index.json";
+
+#[turbo_tasks::function]
+pub async fn app_entry_point_to_route(
+    app_project: Vc<AppProject>,
+    entrypoint: AppEntrypoint,
+) -> Result<Vc<Route>> {
+    Ok(match entrypoint {
+        AppEntrypoint::AppPage { pages, loader_tree } => {
+            let rsc_only = *app_project.project().next_config().rsc_only().await?;
+            Route::AppPage(
+                pages
+                    .into_iter()
+                    .map(|page| {
+                        let rsc_endpoint: Vc<Box<dyn Endpoint>> = Vc::upcast(
+                            AppEndpoint {
+                                ty: AppEndpointType::Page {
+                                    ty: AppPageEndpointType::Rsc,
+                                    loader_tree,
+                                },
+                                app_project,
+                                page: page.clone(),
+                            }
+                            .cell(),
+                        );
+                        // Without HTML rendering, the HTML endpoint is the RSC endpoint, so
+                        // writing both doesn't do any additional work.
+                        let html_endpoint = if rsc_only {
+                            rsc_endpoint
+                        } else {
+                            Vc::upcast(
+                                AppEndpoint {
+                                    ty: AppEndpointType::Page {
+                                        ty: AppPageEndpointType::Html,
+                                        loader_tree,
+                                    },
+                                    app_project,
+                                    page: page.clone(),
+                                }
+                                .cell(),
+                            )
+                        };
+                        AppPageRoute {
+                            original_name: page.to_string(),
+                            html_endpoint,
+                            rsc_endpoint,
+                        }
+                    })
+                    .collect(),
+            )
+        }
+        AppEntrypoint::AppRoute {
+            page,
+            path,
+            root_layouts,
+        } => Route::AppRoute {
+            original_name: page.to_string(),
+            endpoint: Vc::upcast(
+                AppEndpoint {
+                    ty: AppEndpointType::Route { path, root_layouts },
+                    app_project,
+                    page,
+                }
+                .cell(),
+            ),
+        },
+        AppEntrypoint::AppMetadata { page, metadata } => Route::AppRoute {
+            original_name: page.to_string(),
+            endpoint: Vc::upcast(
+                AppEndpoint {
+                    ty: AppEndpointType::Metadata { metadata },
+                    app_project,
+                    page,
+                }
+                .cell(),
+            ),
+        },
+    }
+    .cell())
+}
+
+#[turbo_tasks::function]
+fn client_shared_chunks() -> Vc<RcStr> {
+    Vc::cell("client_shared_chunks".into())
+}
+
+#[turbo_tasks::function]
+fn server_shared_chunks() -> Vc<RcStr> {
+    Vc::cell("server_shared_chunks".into())
+}
+
+#[turbo_tasks::function]
+fn server_entry_dependencies_modifier() -> Vc<RcStr> {
+    Vc::cell("server entry dependencies".into())
+}
+
+/// polyfill-nomodule.js is a pre-compiled asset distributed as part of next,
+/// it's emitted as a RawOutput.
+#[turbo_tasks::function]
+fn polyfill_source(project_path: Vc<FileSystemPath>) -> Vc<FileSource> {
+    FileSource::new(
+        get_next_package(project_path).join("dist/build/polyfills/polyfill-nomodule.js".into()),
+    )
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Debug, TraceRawVcs)]
+enum AppPageEndpointType {
+    Html,
+    Rsc,
+}
+
+impl AppPageEndpointType {
+    /// The HTML and RSC endpoints of a page have the same client chunks, so
+    /// only the HTML endpoint writes the app build manifest, unless only RSC
+    /// endpoints are built.
+    fn emits_app_build_manifest(self, rsc_only: bool) -> bool {
+        rsc_only || matches!(self, AppPageEndpointType::Html)
+    }
+}
+
+/// The build manifests an app endpoint writes to `server/app{prefix}`.
+#[derive(Debug)]
+struct PageBuildManifests {
+    /// `app-build-manifest.json`, if this endpoint writes it.
+    app_build_manifest: Option<AppBuildManifest>,
+    /// `build-manifest.json`.
+    build_manifest: BuildManifest,
+    /// Whether this endpoint writes `css-preload-manifest.json` and
+    /// `error-preload-manifest.json`. The HTML and RSC endpoints of a page
+    /// both write them, with the same content, so either can be built on its
+    /// own.
+    preload_manifests: bool,
+}
+
+/// Decides which build manifests an app endpoint writes and what they
+/// contain. `page_type` is `None` for route handlers.
+fn page_build_manifests(
+    page_type: Option<AppPageEndpointType>,
+    rsc_only: bool,
+    unified_build_manifest: bool,
+    page_name: RcStr,
+    entry_client_chunks_paths: Vec<RcStr>,
+    root_main_files: Vec<RcStr>,
+    polyfill_files: Vec<RcStr>,
+) -> PageBuildManifests {
+    // With a unified build manifest, the pages are merged into the build
+    // manifest instead. The HTML and RSC endpoints both write it to the same
+    // path, so both fill in the page to write the same bytes.
+    let mut pages = HashMap::new();
+    if page_type.is_some_and(|ty| unified_build_manifest || ty.emits_app_build_manifest(rsc_only)) {
+        pages.insert(page_name, entry_client_chunks_paths);
+    }
+    let app_build_manifest = if unified_build_manifest || pages.is_empty() {
+        None
+    } else {
+        Some(AppBuildManifest {
+            pages: std::mem::take(&mut pages),
+        })
+    };
+    PageBuildManifests {
+        app_build_manifest,
+        build_manifest: BuildManifest {
+            root_main_files,
+            polyfill_files,
+            pages,
+            unified: unified_build_manifest,
+            ..Default::default()
+        },
+        preload_manifests: page_type.is_some(),
+    }
+}
 
 #[derive(Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Debug, TraceRawVcs)]
 enum AppEndpointType {
@@ -691,6 +2153,94 @@ enum AppEndpointType {
     },
 }
 
+/// The type of an app route, as classified from its entrypoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "lowercase")]
+pub enum AppRouteType {
+    Page,
+    Route,
+    Metadata,
+}
+
+/// The runtime and type of an app route, e.g. for a badge in the dev overlay.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+pub struct AppRouteRuntime {
+    pub runtime: NextRuntime,
+    #[serde(rename = "type")]
+    pub ty: AppRouteType,
+}
+
+/// The [AppRouteRuntime] of app routes, keyed by their original name.
+#[turbo_tasks::value(transparent)]
+pub struct AppRouteRuntimes(BTreeMap<RcStr, AppRouteRuntime>);
+
+/// The number of output assets an app endpoint produces.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Copy)]
+pub struct AssetCounts {
+    pub client: usize,
+    pub server: usize,
+}
+
+/// The type of a robots or sitemap metadata file, i.e. `robots` or
+/// `sitemap`, or `None` for other files.
+fn robots_or_sitemap_metadata_type(
+    basename: &str,
+    page_extensions: &[RcStr],
+) -> Option<&'static str> {
+    if matches!(
+        match_local_metadata_file(basename, page_extensions),
+        Some(file_match) if file_match.metadata_type == "sitemap"
+    ) {
+        Some("sitemap")
+    } else if matches!(
+        match_global_metadata_file(basename, page_extensions),
+        Some(file_match) if file_match.metadata_type == "robots"
+    ) {
+        Some("robots")
+    } else {
+        None
+    }
+}
+
+/// A content hash of the files written for each app route, keyed by original
+/// name. See [AppProject::route_hashes].
+#[turbo_tasks::value(transparent)]
+pub struct RouteHashes(BTreeMap<RcStr, RcStr>);
+
+/// The original names of the app routes that were added, removed or changed
+/// since a previous build. See [AppProject::diff_routes].
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default)]
+pub struct RoutesDiff {
+    pub added: Vec<RcStr>,
+    pub removed: Vec<RcStr>,
+    pub changed: Vec<RcStr>,
+}
+
+/// The names of an app endpoint's route.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone)]
+pub struct RouteNames {
+    /// The URL pathname of the route, e.g. `/blog/[slug]`. Used for matching
+    /// requests, e.g. in routing tables and middleware matchers.
+    pub pathname: RcStr,
+    /// The name of the route including route groups and the trailing `/page`
+    /// or `/route` segment, e.g. `/(marketing)/blog/[slug]/page`. Used as the
+    /// key in manifests and to derive output paths.
+    pub original_name: RcStr,
+}
+
+/// The paths, relative to the node root, of the files the edge function of an
+/// app endpoint loads.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone)]
+struct EdgeFunctionFiles {
+    files: Vec<RcStr>,
+    wasm: Vec<RcStr>,
+    assets: Vec<RcStr>,
+}
+
 #[turbo_tasks::value]
 struct AppEndpoint {
     ty: AppEndpointType,
@@ -701,15 +2251,19 @@ struct AppEndpoint {
 #[turbo_tasks::value_impl]
 impl AppEndpoint {
     #[turbo_tasks::function]
-    fn app_page_entry(&self, loader_tree: Vc<LoaderTree>) -> Vc<AppEntry> {
-        get_app_page_entry(
+    async fn app_page_entry(&self, loader_tree: Vc<LoaderTree>) -> Result<Vc<AppEntry>> {
+        let loader_tree = match self.app_project.await?.loader_tree_transform {
+            Some(transform) => transform.transform(loader_tree),
+            None => loader_tree,
+        };
+        Ok(get_app_page_entry(
             self.app_project.rsc_module_context(),
             self.app_project.edge_rsc_module_context(),
             loader_tree,
             self.page.clone(),
             self.app_project.project().project_path(),
             self.app_project.project().next_config(),
-        )
+        ))
     }
 
     #[turbo_tasks::function]
@@ -759,6 +2313,7 @@ impl AppEndpoint {
             *self.app_project.project().next_mode().await?,
             metadata,
             next_config,
+            self.app_project.await?.metadata_content_type_resolver,
         ))
     }
 
@@ -767,17 +2322,24 @@ impl AppEndpoint {
         let this = self.await?;
 
         let next_config = self.await?.app_project.project().next_config();
-        let app_entry = match this.ty {
-            AppEndpointType::Page { loader_tree, .. } => self.app_page_entry(loader_tree),
-            AppEndpointType::Route { path, root_layouts } => {
-                self.app_route_entry(path, root_layouts, next_config)
-            }
-            AppEndpointType::Metadata { metadata } => {
-                self.app_metadata_entry(metadata, next_config)
-            }
+        let (app_entry, kind) = match this.ty {
+            AppEndpointType::Page { loader_tree, .. } => (self.app_page_entry(loader_tree), "page"),
+            AppEndpointType::Route { path, root_layouts } => (
+                self.app_route_entry(path, root_layouts, next_config),
+                "route handler",
+            ),
+            AppEndpointType::Metadata { metadata } => (
+                self.app_metadata_entry(metadata, next_config),
+                "metadata route",
+            ),
         };
 
-        Ok(app_entry)
+        // Resolving the entry runs the task creating it, so its errors surface here
+        // and can be attributed to the endpoint.
+        app_entry
+            .resolve()
+            .await
+            .with_context(|| format!("failed to create the {kind} entry of {}", this.page))
     }
 
     #[turbo_tasks::function]
@@ -785,11 +2347,180 @@ impl AppEndpoint {
         self.output().output_assets()
     }
 
+    /// The output assets as written to disk, after applying the registered
+    /// [OutputAssetPostProcessor]s and adding debug IDs when enabled.
+    #[turbo_tasks::function]
+    async fn emitted_output_assets(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        let app_project = self.await?.app_project;
+        let assets = post_process_output_assets(
+            all_assets_from_entries(self.output_assets()),
+            app_project.await?.output_asset_post_processors.clone(),
+        );
+        Ok(if *app_project.project().next_config().debug_ids().await? {
+            inject_debug_ids(assets)
+        } else {
+            assets
+        })
+    }
+
+    /// The SSR chunks of all client components of the endpoint. These are
+    /// empty for endpoints which don't server-side render client components,
+    /// i.e. everything but the HTML endpoint of pages.
+    #[turbo_tasks::function]
+    pub fn client_component_ssr_chunks(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output().client_component_ssr_chunks()
+    }
+
+    /// The paths of the font files the endpoint's client assets reference,
+    /// relative to the client root. Unlike the next-font manifest, this
+    /// includes fonts that aren't preloaded.
+    #[turbo_tasks::function]
+    pub async fn used_fonts(self: Vc<Self>) -> Result<Vc<Vec<RcStr>>> {
+        Ok(used_font_paths(
+            self.await?.app_project.project().client_root(),
+            self.output().client_assets(),
+        ))
+    }
+
+    /// The path of the polyfill chunk as listed in the build manifest, or
+    /// `None` if polyfills are disabled or the endpoint has no client side.
+    #[turbo_tasks::function]
+    pub async fn polyfill_path(self: Vc<Self>) -> Result<Vc<Option<RcStr>>> {
+        let this = self.await?;
+        let project = this.app_project.project();
+        if !matches!(this.ty, AppEndpointType::Page { .. })
+            || *project.next_config().disable_polyfill().await?
+        {
+            return Ok(Vc::cell(None));
+        }
+
+        let polyfill_output_path = project.client_chunking_context().chunk_path(
+            polyfill_source(project.project_path()).ident(),
+            ".js".into(),
+        );
+        let polyfill_client_path = get_path_in_root(
+            &*project.client_relative_path().await?,
+            &*polyfill_output_path.await?,
+            "polyfill",
+            &self.route_names().await?.original_name,
+        )?
+        .into();
+        Ok(Vc::cell(Some(
+            transform_asset_path(
+                this.app_project.await?.asset_path_transform,
+                polyfill_client_path,
+            )
+            .await?,
+        )))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn route_names(self: Vc<Self>) -> Result<Vc<RouteNames>> {
+        let app_entry = self.app_endpoint_entry().await?;
+        Ok(RouteNames {
+            pathname: app_entry.pathname.clone(),
+            original_name: app_entry.original_name.clone(),
+        }
+        .cell())
+    }
+
+    /// Whether the endpoint intercepts another route, i.e. a segment of its page
+    /// starts with `(.)`, `(..)` or `(...)`. This doesn't build the endpoint.
+    #[turbo_tasks::function]
+    pub async fn is_interception_route(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(self.await?.page.is_interception_route()))
+    }
+
+    /// The number of client and server assets the endpoint produces. This
+    /// builds the endpoint, but doesn't write it to disk.
     #[turbo_tasks::function]
-    async fn output(self: Vc<Self>) -> Result<Vc<AppEndpointOutput>> {
+    pub async fn asset_counts(self: Vc<Self>) -> Result<Vc<AssetCounts>> {
+        let output = self.output();
+        Ok(AssetCounts {
+            client: output.client_assets().await?.len(),
+            server: output.server_assets().await?.len(),
+        }
+        .cell())
+    }
+
+    /// The segment config of the endpoint's entry, merged with the configs of
+    /// its layouts, or of its root layouts for route handlers.
+    #[turbo_tasks::function]
+    pub async fn resolved_segment_config(self: Vc<Self>) -> Result<Vc<NextSegmentConfig>> {
+        Ok(self.app_endpoint_entry().await?.config)
+    }
+
+    /// The HTTP methods the route handler of the endpoint exports, in the order
+    /// of `HTTP_METHODS`. Fails for endpoints that aren't route handlers.
+    #[turbo_tasks::function]
+    pub async fn as_route_handler_methods(self: Vc<Self>) -> Result<Vc<AppRouteMethods>> {
         let this = self.await?;
+        let AppEndpointType::Route { path, .. } = this.ty else {
+            bail!("{} is not a route handler", this.page);
+        };
+        Ok(get_app_route_methods(Vc::upcast(FileSource::new(path))))
+    }
 
+    /// The name and matcher the endpoint has in the middleware manifest when
+    /// it's built for the edge runtime.
+    #[turbo_tasks::function]
+    async fn edge_function_matcher(self: Vc<Self>) -> Result<Vc<EdgeFunctionMatcher>> {
+        let this = self.await?;
         let app_entry = self.app_endpoint_entry().await?;
+        let base_path = this.app_project.project().next_config().base_path().await?;
+        let source: RcStr = with_base_path(
+            base_path.as_deref().unwrap_or_default(),
+            &app_entry.pathname,
+        )
+        .into();
+        let regex = this.app_project.middleware_regex(source.clone()).await?;
+        Ok(EdgeFunctionMatcher {
+            name: app_entry.pathname.clone(),
+            original_source: source,
+            regex: regex.clone_value(),
+        }
+        .cell())
+    }
+
+    #[turbo_tasks::function]
+    fn output(self: Vc<Self>) -> Vc<AppEndpointOutput> {
+        self.output_with_runtime(None)
+    }
+
+    /// Builds the endpoint for `runtime`, ignoring the runtime from its segment
+    /// config. This is meant for comparing the edge and Node.js output of a
+    /// route in tests and tooling, and must not be used when building.
+    ///
+    /// Forcing the edge runtime on a route that isn't compatible with it may
+    /// produce issues that wouldn't occur in a regular build.
+    #[turbo_tasks::function]
+    pub fn output_for_runtime(self: Vc<Self>, runtime: NextRuntime) -> Vc<AppEndpointOutput> {
+        self.output_with_runtime(Some(runtime))
+    }
+
+    #[turbo_tasks::function]
+    async fn output_with_runtime(
+        self: Vc<Self>,
+        runtime: Option<NextRuntime>,
+    ) -> Result<Vc<AppEndpointOutput>> {
+        let this = self.await?;
+
+        let build = InFlightBuild::start(self).await?;
+
+        let mut memory_report = MemoryReport::new(
+            *this
+                .app_project
+                .project()
+                .next_config()
+                .endpoint_memory_report()
+                .await?,
+        );
+
+        let app_entry = self.app_endpoint_entry().await?;
+        memory_report.record("entry");
+        if build.is_cancelled() {
+            return Ok(AppEndpointOutput::Cancelled.cell());
+        }
 
         let (process_client, process_ssr) = match this.ty {
             AppEndpointType::Page { ty, .. } => (true, matches!(ty, AppPageEndpointType::Html)),
@@ -812,7 +2543,45 @@ impl AppEndpoint {
         // assets to add to the middleware manifest (to be loaded in the edge runtime).
         let mut middleware_assets = vec![];
 
-        let runtime = app_entry.config.await?.runtime.unwrap_or_default();
+        let runtime = match runtime {
+            Some(runtime) => runtime,
+            None => app_entry.config.await?.runtime.unwrap_or_default(),
+        };
+
+        // The route name is used as key in the manifests, but output paths may need a
+        // sanitized version of it.
+        let mut manifest_path_prefix = if *this
+            .app_project
+            .project()
+            .next_config()
+            .sanitize_route_paths()
+            .await?
+        {
+            sanitize_route_path(&app_entry.original_name)
+        } else {
+            app_entry.original_name.clone()
+        };
+        let mut manifest_page_name = app_entry.original_name.clone();
+        let mut manifest_pathname = app_entry.pathname.clone();
+        let app_project = this.app_project.await?;
+        if let Some(variant) = &app_project.variant {
+            manifest_path_prefix = format!("{manifest_path_prefix}{variant}").into();
+            if app_project.variant_page_names {
+                manifest_page_name = format!("{manifest_page_name}{variant}").into();
+                manifest_pathname = format!("{manifest_pathname}{variant}").into();
+            }
+        }
+        let app_paths_manifest_keys = match *this
+            .app_project
+            .project()
+            .next_config()
+            .app_paths_manifest_key()
+            .await?
+        {
+            AppPathsManifestKey::OriginalName => vec![manifest_page_name.clone()],
+            AppPathsManifestKey::Pathname => vec![manifest_pathname],
+            AppPathsManifestKey::Both => vec![manifest_page_name.clone(), manifest_pathname],
+        };
 
         let rsc_entry = app_entry.rsc_entry;
 
@@ -820,6 +2589,14 @@ impl AppEndpoint {
 
         let client_chunking_context = this.app_project.project().client_chunking_context();
 
+        let asset_path_transform = this.app_project.await?.asset_path_transform;
+
+        // SSR chunks of the client components, only computed for HTML page endpoints.
+        let mut client_component_ssr_chunks = OutputAssets::empty();
+
+        // Availability of the client shared chunks, which are loaded on every page.
+        let mut client_base_availability_info = None;
+
         let (app_server_reference_modules, client_dynamic_imports) = if process_client {
             let client_shared_chunk_group = get_app_client_shared_chunk_group(
                 AssetIdent::from_path(this.app_project.project().project_path())
@@ -836,15 +2613,55 @@ impl AppEndpoint {
                 let chunk_path = chunk.ident().path().await?;
                 if chunk_path.extension_ref() == Some("js") {
                     if let Some(chunk_path) = client_relative_path_ref.get_path_to(&chunk_path) {
-                        client_shared_chunks_paths.push(chunk_path.into());
+                        client_shared_chunks_paths.push(
+                            transform_asset_path(asset_path_transform, chunk_path.into()).await?,
+                        );
                     }
                 }
             }
+            if *this
+                .app_project
+                .project()
+                .next_config()
+                .sort_root_main_files()
+                .await?
+            {
+                // The last chunk of an evaluated chunk group is the one evaluating the entries
+                // once all other chunks are loaded, so only the chunks before it are sorted.
+                if let Some((_, chunks)) = client_shared_chunks_paths.split_last_mut() {
+                    chunks.sort();
+                }
+            }
             let client_shared_availability_info = client_shared_chunk_group.availability_info;
+            client_base_availability_info = Some(client_shared_availability_info);
 
             let client_references = client_reference_graph(Vc::cell(vec![rsc_entry_asset]));
             let client_reference_types = client_references.types();
 
+            if let Some(mode) = *this
+                .app_project
+                .project()
+                .next_config()
+                .client_reference_cycles()
+                .await?
+            {
+                let cycles = client_reference_cycles(client_references).await?;
+                for cycle in cycles.iter() {
+                    ClientReferenceCycleIssue {
+                        cycle: cycle.clone(),
+                    }
+                    .cell()
+                    .emit();
+                }
+                if matches!(mode, ClientReferenceCyclesMode::Fail) && !cycles.is_empty() {
+                    bail!(
+                        "found {} client reference cycle(s) in {}",
+                        cycles.len(),
+                        this.page
+                    );
+                }
+            }
+
             let ssr_chunking_context = if process_ssr {
                 Some(match runtime {
                     NextRuntime::NodeJs => {
@@ -859,7 +2676,7 @@ impl AppEndpoint {
                 None
             };
 
-            let client_dynamic_imports = collect_next_dynamic_imports(
+            let mut client_dynamic_imports = collect_next_dynamic_imports(
                 client_references
                     .await?
                     .client_references
@@ -875,6 +2692,34 @@ impl AppEndpoint {
             )
             .await?;
 
+            // CSS client references can't contain `next/dynamic` imports, but their client
+            // modules are chunked along with the dynamic imports so they can be preloaded.
+            for client_reference in client_references.await?.client_references.iter() {
+                if let ClientReferenceType::CssClientReference(css_client_reference) =
+                    client_reference.ty()
+                {
+                    let client_module: Vc<Box<dyn Module>> =
+                        Vc::upcast(css_client_reference.await?.client_module);
+                    client_dynamic_imports
+                        .entry(Vc::upcast(css_client_reference))
+                        .or_default()
+                        .push((
+                            client_module.ident().to_string().await?.clone_value(),
+                            client_module,
+                        ));
+                }
+            }
+
+            if let AppEndpointType::Page { loader_tree, .. } = this.ty {
+                let root_layouts = loader_tree.await?.root_layouts();
+                for &path in client_reference_root_layouts(client_references, root_layouts)
+                    .await?
+                    .iter()
+                {
+                    RootLayoutClientReferenceIssue { path }.cell().emit();
+                }
+            }
+
             let client_references_chunks = get_app_client_references_chunks(
                 client_references,
                 client_chunking_context,
@@ -883,6 +2728,24 @@ impl AppEndpoint {
             );
             let client_references_chunks_ref = client_references_chunks.await?;
 
+            if *this
+                .app_project
+                .project()
+                .next_config()
+                .report_duplicate_client_modules()
+                .await?
+            {
+                let duplicates = duplicate_client_chunk_modules(client_references_chunks).await?;
+                for &(module, chunk_count) in duplicates.iter() {
+                    DuplicateClientModuleIssue {
+                        module,
+                        chunk_count,
+                    }
+                    .cell()
+                    .emit();
+                }
+            }
+
             let mut entry_client_chunks = IndexSet::new();
             // TODO(alexkirsz) In which manifest does this go?
             let mut entry_ssr_chunks = IndexSet::new();
@@ -907,6 +2770,7 @@ impl AppEndpoint {
 
             client_assets.extend(entry_client_chunks.iter().copied());
             server_assets.extend(entry_ssr_chunks.iter().copied());
+            client_component_ssr_chunks = OutputAssets::new(entry_ssr_chunks.into_iter().collect());
 
             let entry_client_chunks_paths = entry_client_chunks
                 .iter()
@@ -916,52 +2780,65 @@ impl AppEndpoint {
             let mut entry_client_chunks_paths = entry_client_chunks_paths
                 .iter()
                 .map(|path| {
-                    Ok(client_relative_path_ref
-                        .get_path_to(path)
-                        .context("asset path should be inside client root")?
-                        .into())
+                    Ok(get_path_in_root(
+                        &client_relative_path_ref,
+                        path,
+                        "client chunk",
+                        &app_entry.original_name,
+                    )?
+                    .into())
                 })
                 .collect::<anyhow::Result<Vec<_>>>()?;
+            for path in entry_client_chunks_paths.iter_mut() {
+                *path = transform_asset_path(asset_path_transform, path.clone()).await?;
+            }
             entry_client_chunks_paths.extend(client_shared_chunks_paths.iter().cloned());
 
-            let app_build_manifest = AppBuildManifest {
-                pages: [(app_entry.original_name.clone(), entry_client_chunks_paths)]
-                    .into_iter()
-                    .collect(),
-            };
-            let manifest_path_prefix = &app_entry.original_name;
-            let app_build_manifest_output = Vc::upcast(VirtualOutputAsset::new(
-                node_root.join(
-                    format!("server/app{manifest_path_prefix}/app-build-manifest.json",).into(),
-                ),
-                AssetContent::file(
-                    File::from(serde_json::to_string_pretty(&app_build_manifest)?).into(),
-                ),
-            ));
-            server_assets.push(app_build_manifest_output);
+            let unified_build_manifest = *this
+                .app_project
+                .project()
+                .next_config()
+                .unified_build_manifest()
+                .await?;
+            let rsc_only = *this.app_project.project().next_config().rsc_only().await?;
+
+            let mut polyfill_client_paths = vec![];
+            if let Some(polyfill_client_path) = &*self.polyfill_path().await? {
+                let polyfill_source = polyfill_source(this.app_project.project().project_path());
+                let polyfill_output_asset = RawOutput::new(
+                    client_chunking_context.chunk_path(polyfill_source.ident(), ".js".into()),
+                    Vc::upcast(polyfill_source),
+                );
+                polyfill_client_paths.push(polyfill_client_path.clone());
+                client_assets.push(Vc::upcast(polyfill_output_asset));
+            }
 
-            // polyfill-nomodule.js is a pre-compiled asset distributed as part of next,
-            // load it as a RawModule.
-            let next_package = get_next_package(this.app_project.project().project_path());
-            let polyfill_source = FileSource::new(
-                next_package.join("dist/build/polyfills/polyfill-nomodule.js".into()),
+            let PageBuildManifests {
+                app_build_manifest,
+                build_manifest,
+                preload_manifests,
+            } = page_build_manifests(
+                match this.ty {
+                    AppEndpointType::Page { ty, .. } => Some(ty),
+                    _ => None,
+                },
+                rsc_only,
+                unified_build_manifest,
+                manifest_page_name.clone(),
+                entry_client_chunks_paths,
+                client_shared_chunks_paths,
+                polyfill_client_paths,
             );
-            let polyfill_output_path =
-                client_chunking_context.chunk_path(polyfill_source.ident(), ".js".into());
-            let polyfill_output_asset =
-                RawOutput::new(polyfill_output_path, Vc::upcast(polyfill_source));
-            let polyfill_client_path = client_relative_path_ref
-                .get_path_to(&*polyfill_output_path.await?)
-                .context("failed to resolve client-relative path to polyfill")?
-                .into();
-            let polyfill_client_paths = vec![polyfill_client_path];
-            client_assets.push(Vc::upcast(polyfill_output_asset));
-
-            let build_manifest = BuildManifest {
-                root_main_files: client_shared_chunks_paths,
-                polyfill_files: polyfill_client_paths,
-                ..Default::default()
-            };
+            if let Some(app_build_manifest) = app_build_manifest {
+                server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                    node_root.join(
+                        format!("server/app{manifest_path_prefix}/app-build-manifest.json",).into(),
+                    ),
+                    AssetContent::file(
+                        File::from(serde_json::to_string_pretty(&app_build_manifest)?).into(),
+                    ),
+                )));
+            }
             let build_manifest_output = Vc::upcast(VirtualOutputAsset::new(
                 node_root
                     .join(format!("server/app{manifest_path_prefix}/build-manifest.json",).into()),
@@ -971,34 +2848,150 @@ impl AppEndpoint {
             ));
             server_assets.push(build_manifest_output);
 
+            if let (true, AppEndpointType::Page { loader_tree, .. }) = (preload_manifests, this.ty)
+            {
+                // Layout segments are ordered from the root layout to the page, so their
+                // CSS comes before the CSS of the client components they render.
+                let mut css_preload_manifest = CssPreloadManifest::default();
+                let css_chunks = client_references_chunks_ref
+                    .layout_segment_client_chunks
+                    .values()
+                    .chain(
+                        client_references_chunks_ref
+                            .client_component_client_chunks
+                            .values()
+                            .map(|(chunks, _)| chunks),
+                    );
+                for chunks in css_chunks {
+                    for chunk in chunks.await?.iter() {
+                        let path = chunk.ident().path().await?;
+                        if path.extension_ref() != Some("css") {
+                            continue;
+                        }
+                        let path = transform_asset_path(
+                            asset_path_transform,
+                            get_path_in_root(
+                                &client_relative_path_ref,
+                                &path,
+                                "CSS chunk",
+                                &app_entry.original_name,
+                            )?
+                            .into(),
+                        )
+                        .await?;
+                        if !css_preload_manifest.files.contains(&path) {
+                            css_preload_manifest.files.push(path);
+                        }
+                    }
+                }
+                server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                    node_root.join(
+                        format!("server/app{manifest_path_prefix}/css-preload-manifest.json")
+                            .into(),
+                    ),
+                    AssetContent::file(
+                        File::from(serde_json::to_string_pretty(&css_preload_manifest)?).into(),
+                    ),
+                )));
+
+                let error_boundaries = client_reference_error_boundaries(
+                    client_references,
+                    loader_tree.await?.error_boundaries(),
+                )
+                .await?;
+                let mut error_preload_manifest = ErrorPreloadManifest::default();
+                for error_boundary in error_boundaries.iter() {
+                    let Some((chunks, _)) = client_references_chunks_ref
+                        .client_component_client_chunks
+                        .get(error_boundary)
+                    else {
+                        continue;
+                    };
+                    for chunk in chunks.await?.iter() {
+                        let path = transform_asset_path(
+                            asset_path_transform,
+                            get_path_in_root(
+                                &client_relative_path_ref,
+                                &*chunk.ident().path().await?,
+                                "error boundary chunk",
+                                &app_entry.original_name,
+                            )?
+                            .into(),
+                        )
+                        .await?;
+                        if !error_preload_manifest.files.contains(&path) {
+                            error_preload_manifest.files.push(path);
+                        }
+                    }
+                }
+                if !error_preload_manifest.files.is_empty() {
+                    server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                        node_root.join(
+                            format!("server/app{manifest_path_prefix}/error-preload-manifest.json")
+                                .into(),
+                        ),
+                        AssetContent::file(
+                            File::from(serde_json::to_string_pretty(&error_preload_manifest)?)
+                                .into(),
+                        ),
+                    )));
+                }
+            }
+
             let entry_manifest = ClientReferenceManifest::build_output(
                 node_root,
                 client_relative_path,
                 app_entry.original_name.clone(),
+                manifest_path_prefix.clone(),
                 client_references,
                 client_references_chunks,
                 client_chunking_context,
                 ssr_chunking_context,
                 this.app_project.project().next_config(),
                 runtime,
+                asset_path_transform,
             );
             server_assets.push(entry_manifest);
 
+            if *this
+                .app_project
+                .project()
+                .next_config()
+                .env_manifest()
+                .await?
+            {
+                server_assets.push(create_env_manifest(
+                    node_root
+                        .join(format!("server/app{manifest_path_prefix}/env-manifest.json").into()),
+                    Vc::cell(client_assets.clone()),
+                    this.app_project.project().client_define_env(),
+                ));
+            }
+
             if runtime == NextRuntime::Edge {
                 middleware_assets.push(entry_manifest);
 
                 // as the edge runtime doesn't support chunk loading we need to add all client
                 // references to the middleware manifest so they get loaded during runtime
-                // initialization
-                let client_references_chunks = &*client_references_chunks.await?;
-
-                for (ssr_chunks, _) in client_references_chunks
-                    .client_component_ssr_chunks
-                    .values()
+                // initialization, unless the platform is known to support it. The SSR chunks
+                // are server assets either way.
+                if !*this
+                    .app_project
+                    .project()
+                    .next_config()
+                    .edge_chunk_loading()
+                    .await?
                 {
-                    let ssr_chunks = ssr_chunks.await?;
+                    let client_references_chunks = &*client_references_chunks.await?;
+
+                    for (ssr_chunks, _) in client_references_chunks
+                        .client_component_ssr_chunks
+                        .values()
+                    {
+                        let ssr_chunks = ssr_chunks.await?;
 
-                    middleware_assets.extend(ssr_chunks);
+                        middleware_assets.extend(ssr_chunks);
+                    }
                 }
             }
 
@@ -1009,19 +3002,40 @@ impl AppEndpoint {
         } else {
             (None, None)
         };
+        memory_report.record("client references and chunks");
+        if build.is_cancelled() {
+            return Ok(AppEndpointOutput::Cancelled.cell());
+        }
 
+        /// Creates the app paths manifest with an entry for each of `keys`.
         fn create_app_paths_manifest(
             node_root: Vc<FileSystemPath>,
-            original_name: &str,
+            keys: &[RcStr],
+            manifest_path_prefix: &str,
             filename: RcStr,
+            config: &NextSegmentConfig,
+            route_groups: Vec<RcStr>,
         ) -> Result<Vc<Box<dyn OutputAsset>>> {
-            let manifest_path_prefix = original_name;
+            fn entries<T: Clone>(keys: &[RcStr], value: Option<T>) -> HashMap<RcStr, T> {
+                value
+                    .into_iter()
+                    .flat_map(|value| keys.iter().map(move |key| (key.clone(), value.clone())))
+                    .collect()
+            }
+
             let path = node_root
                 .join(format!("server/app{manifest_path_prefix}/app-paths-manifest.json",).into());
             let app_paths_manifest = AppPathsManifest {
                 node_server_app_paths: PagesManifest {
-                    pages: [(original_name.into(), filename)].into_iter().collect(),
+                    pages: entries(keys, Some(filename)),
                 },
+                cache_control: entries(keys, config.cache_control_hint()),
+                fetch_cache: entries(keys, config.fetch_cache),
+                route_groups: entries(
+                    keys,
+                    Some(route_groups).filter(|route_groups| !route_groups.is_empty()),
+                ),
+                streaming: entries(keys, config.streaming),
                 ..Default::default()
             };
             Ok(Vc::upcast(VirtualOutputAsset::new(
@@ -1032,14 +3046,94 @@ impl AppEndpoint {
             )))
         }
 
+        if let Some(revalidate) = app_entry.config.await?.revalidate {
+            let app_revalidate_manifest = AppRevalidateManifest {
+                pages: [(manifest_page_name.clone(), revalidate.into())]
+                    .into_iter()
+                    .collect(),
+            };
+            server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                node_root.join(
+                    format!("server/app{manifest_path_prefix}/app-revalidate-manifest.json").into(),
+                ),
+                AssetContent::file(
+                    File::from(serde_json::to_string_pretty(&app_revalidate_manifest)?).into(),
+                ),
+            )));
+        }
+
+        let route_headers = this
+            .app_project
+            .project()
+            .next_config()
+            .route_headers(app_entry.original_name.clone())
+            .await?;
+        if !route_headers.is_empty() {
+            let headers_manifest = HeadersManifest {
+                pages: [(manifest_page_name.clone(), &route_headers[..])]
+                    .into_iter()
+                    .collect(),
+            };
+            server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                node_root
+                    .join(format!("server/app{manifest_path_prefix}/headers-manifest.json").into()),
+                AssetContent::file(
+                    File::from(serde_json::to_string_pretty(&headers_manifest)?).into(),
+                ),
+            )));
+        }
+
+        if *this
+            .app_project
+            .project()
+            .next_config()
+            .sources_manifest()
+            .await?
+        {
+            let project_root_path = this.app_project.project().project_root_path().await?;
+            let mut sources_manifest = SourcesManifest::default();
+            for module in all_modules_and_affecting_sources(rsc_entry_asset)
+                .await?
+                .iter()
+            {
+                let path = module.ident().path().await?;
+                // Skips modules that aren't backed by a file, e.g. virtual modules.
+                if let Some(path) = project_root_path.get_path_to(&path) {
+                    sources_manifest.files.insert(path.into());
+                }
+            }
+            server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                node_root
+                    .join(format!("server/app{manifest_path_prefix}/sources-manifest.json").into()),
+                AssetContent::file(
+                    File::from(serde_json::to_string_pretty(&sources_manifest)?).into(),
+                ),
+            )));
+        }
+
         let client_assets = OutputAssets::new(client_assets);
 
+        let server_actions_manifest_name = this
+            .app_project
+            .project()
+            .next_config()
+            .server_actions_manifest_name()
+            .await?;
+        let compress_manifests = *this
+            .app_project
+            .project()
+            .next_config()
+            .compress_manifests()
+            .await?;
+        let compact_manifests = *this.app_project.project().compact_manifests().await?;
+        let analyze = *this.app_project.project().next_config().analyze().await?;
+
         let next_font_manifest_output = create_font_manifest(
             this.app_project.project().client_root(),
             node_root,
             this.app_project.app_dir(),
             &app_entry.original_name,
-            &app_entry.original_name,
+            &manifest_path_prefix,
             &app_entry.original_name,
             client_assets,
             true,
@@ -1047,8 +3141,44 @@ impl AppEndpoint {
         .await?;
         server_assets.push(next_font_manifest_output);
 
+        if let AppEndpointType::Route { .. } = this.ty {
+            if *this
+                .app_project
+                .project()
+                .next_config()
+                .route_openapi_stubs()
+                .await?
+            {
+                server_assets.push(create_openapi_stub(
+                    node_root,
+                    manifest_path_prefix.clone(),
+                    this.page.clone(),
+                    self.as_route_handler_methods(),
+                ));
+            }
+        }
+
         let endpoint_output = match runtime {
             NextRuntime::Edge => {
+                if *this
+                    .app_project
+                    .project()
+                    .next_config()
+                    .validate_edge_node_builtins()
+                    .await?
+                {
+                    let imports =
+                        edge_node_builtin_imports(Vc::upcast(app_entry.rsc_entry)).await?;
+                    for (importer, builtin) in imports.iter() {
+                        EdgeNodeBuiltinImportIssue {
+                            importer: *importer,
+                            builtin: builtin.clone(),
+                        }
+                        .cell()
+                        .emit();
+                    }
+                }
+
                 // create edge chunks
                 let chunking_context = this
                     .app_project
@@ -1064,6 +3194,7 @@ impl AppEndpoint {
                     .context("Entry module must be evaluatable")?;
                 evaluatable_assets.push(evaluatable);
 
+                let mut server_action_ids = Vc::cell(vec![]);
                 if let Some(app_server_reference_modules) = app_server_reference_modules {
                     let (loader, manifest) = create_server_actions_manifest(
                         Vc::upcast(app_entry.rsc_entry),
@@ -1071,6 +3202,8 @@ impl AppEndpoint {
                         this.app_project.project().project_path(),
                         node_root,
                         &app_entry.original_name,
+                        &manifest_path_prefix,
+                        &server_actions_manifest_name,
                         NextRuntime::Edge,
                         Vc::upcast(this.app_project.edge_rsc_module_context()),
                         Vc::upcast(chunking_context),
@@ -1078,6 +3211,11 @@ impl AppEndpoint {
                     .await?;
                     server_assets.push(manifest);
                     evaluatable_assets.push(loader);
+                    server_action_ids = get_server_action_ids(
+                        Vc::upcast(app_entry.rsc_entry),
+                        app_server_reference_modules,
+                        Vc::upcast(this.app_project.edge_rsc_module_context()),
+                    );
                 }
 
                 let files = chunking_context.evaluated_chunk_group_assets(
@@ -1089,12 +3227,33 @@ impl AppEndpoint {
 
                 server_assets.extend(files_value.iter().copied());
 
+                if let Some(limit) = *this
+                    .app_project
+                    .project()
+                    .next_config()
+                    .edge_function_size_limit()
+                    .await?
+                {
+                    let size =
+                        total_size(&files_value).await? + total_size(&middleware_assets).await?;
+                    if size > limit {
+                        EdgeFunctionSizeIssue {
+                            file_path: app_entry.rsc_entry.ident().path(),
+                            original_name: app_entry.original_name.clone(),
+                            size,
+                            limit,
+                        }
+                        .cell()
+                        .emit();
+                    }
+                }
+
                 // the next-edge-ssr-loader templates expect the manifests to be stored in
                 // global variables defined in these files
                 //
                 // they are created in `setup-dev-bundler.ts`
                 let mut file_paths_from_root = vec![
-                    "server/server-reference-manifest.js".into(),
+                    format!("server/{server_actions_manifest_name}.js").into(),
                     "server/middleware-build-manifest.js".into(),
                     "server/middleware-react-loadable-manifest.js".into(),
                     "server/next-font-manifest.js".into(),
@@ -1123,17 +3282,26 @@ impl AppEndpoint {
                 let entry_file = "app-edge-has-no-entrypoint".into();
 
                 // create middleware manifest
-                let named_regex = get_named_middleware_regex(&app_entry.pathname);
+                let EdgeFunctionMatcher {
+                    name,
+                    original_source,
+                    regex,
+                } = self.edge_function_matcher().await?.clone_value();
                 let matchers = MiddlewareMatcher {
-                    regexp: Some(named_regex.into()),
-                    original_source: app_entry.pathname.clone(),
+                    regexp: Some(regex),
+                    original_source,
                     ..Default::default()
                 };
-                let edge_function_definition = EdgeFunctionDefinition {
+                let edge_function_files = EdgeFunctionFiles {
                     files: file_paths_from_root,
-                    wasm: wasm_paths_to_bindings(wasm_paths_from_root),
-                    assets: paths_to_bindings(all_assets),
-                    name: app_entry.pathname.clone(),
+                    wasm: wasm_paths_from_root,
+                    assets: all_assets,
+                };
+                let edge_function_definition = EdgeFunctionDefinition {
+                    files: edge_function_files.files.clone(),
+                    wasm: wasm_paths_to_bindings(edge_function_files.wasm.clone()),
+                    assets: paths_to_bindings(edge_function_files.assets.clone()),
+                    name,
                     page: app_entry.original_name.clone(),
                     regions: app_entry
                         .config
@@ -1151,8 +3319,30 @@ impl AppEndpoint {
                         .collect(),
                     ..Default::default()
                 };
-                let manifest_path_prefix = &app_entry.original_name;
-                let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
+                if *this
+                    .app_project
+                    .project()
+                    .next_config()
+                    .legacy_middleware_manifest()
+                    .await?
+                {
+                    let middleware_manifest_v1 = MiddlewaresManifest::MiddlewaresManifestV1(
+                        MiddlewaresManifestV1::from_v2(&middleware_manifest_v2),
+                    );
+                    server_assets.push(Vc::upcast(VirtualOutputAsset::new(
+                        node_root.join(
+                            format!(
+                                "server/app{manifest_path_prefix}/legacy-middleware-manifest.json"
+                            )
+                            .into(),
+                        ),
+                        AssetContent::file(
+                            File::from(serde_json::to_string_pretty(&middleware_manifest_v1)?)
+                                .into(),
+                        ),
+                    )));
+                }
+                server_assets.push(Vc::upcast(VirtualOutputAsset::new(
                     node_root.join(
                         format!("server/app{manifest_path_prefix}/middleware-manifest.json",)
                             .into(),
@@ -1163,12 +3353,17 @@ impl AppEndpoint {
                         )?))
                         .cell(),
                     ),
-                ));
-                server_assets.push(middleware_manifest_v2);
+                )));
 
                 // create app paths manifest
-                let app_paths_manifest_output =
-                    create_app_paths_manifest(node_root, &app_entry.original_name, entry_file)?;
+                let app_paths_manifest_output = create_app_paths_manifest(
+                    node_root,
+                    &app_paths_manifest_keys,
+                    &manifest_path_prefix,
+                    entry_file,
+                    &*app_entry.config.await?,
+                    this.page.route_groups(),
+                )?;
                 server_assets.push(app_paths_manifest_output);
 
                 // create react-loadable-manifest for next/dynamic
@@ -1187,30 +3382,90 @@ impl AppEndpoint {
                     dynamic_import_entries,
                     client_relative_path,
                     node_root.join(
-                        format!(
-                            "server/app{}/react-loadable-manifest.json",
-                            &app_entry.original_name
-                        )
-                        .into(),
+                        format!("server/app{manifest_path_prefix}/react-loadable-manifest.json")
+                            .into(),
                     ),
+                    this.app_project
+                        .project()
+                        .next_config()
+                        .loadable_manifest_format(),
                 );
                 server_assets.extend(loadable_manifest_output.await?.iter().copied());
 
+                if *this
+                    .app_project
+                    .project()
+                    .next_config()
+                    .dynamic_imports_manifest()
+                    .await?
+                {
+                    server_assets.push(create_dynamic_imports_manifest(
+                        dynamic_import_entries,
+                        client_relative_path,
+                        node_root.join(
+                            format!(
+                                "server/app{manifest_path_prefix}/dynamic-imports-manifest.json"
+                            )
+                            .into(),
+                        ),
+                    ));
+                }
+
+                if analyze {
+                    server_assets.push(create_route_stats(
+                        node_root
+                            .join(format!("server/app{manifest_path_prefix}/stats.json").into()),
+                        this.app_project.project().client_root(),
+                        client_assets,
+                        node_root,
+                        Vc::cell(server_assets.clone()),
+                    ));
+                }
+
+                if compact_manifests {
+                    server_assets = compact_json_manifests(server_assets).await?;
+                }
+                if compress_manifests {
+                    server_assets = gzip_manifests(server_assets).await?;
+                }
+
                 AppEndpointOutput::Edge {
                     files,
+                    edge_function_files: edge_function_files.cell(),
                     server_assets: Vc::cell(server_assets),
                     client_assets,
+                    client_component_ssr_chunks,
+                    server_action_ids,
+                    middleware_manifest: EndpointMiddlewareManifest {
+                        manifest: Some(middleware_manifest_v2),
+                    }
+                    .cell(),
                 }
             }
             NextRuntime::NodeJs => {
                 let mut evaluatable_assets =
                     this.app_project.rsc_runtime_entries().await?.clone_value();
 
-                let chunking_context = this
-                    .app_project
-                    .project()
-                    .server_chunking_context(process_client);
+                // An unsplit route handler is bundled into its own chunks, which aren't
+                // shared with other endpoints, so it can be deployed on its own.
+                let unsplit = matches!(this.ty, AppEndpointType::Route { .. })
+                    && *this
+                        .app_project
+                        .project()
+                        .next_config()
+                        .unsplit_route_handlers()
+                        .await?;
+                let chunking_context = if unsplit {
+                    this.app_project
+                        .project()
+                        .unsplit_server_chunking_context(process_client)
+                } else {
+                    this.app_project
+                        .project()
+                        .server_chunking_context(process_client)
+                };
 
+                let mut server_action_ids = Vc::cell(vec![]);
                 if let Some(app_server_reference_modules) = app_server_reference_modules {
                     let (loader, manifest) = create_server_actions_manifest(
                         Vc::upcast(app_entry.rsc_entry),
@@ -1218,6 +3473,8 @@ impl AppEndpoint {
                         this.app_project.project().project_path(),
                         node_root,
                         &app_entry.original_name,
+                        &manifest_path_prefix,
+                        &server_actions_manifest_name,
                         NextRuntime::NodeJs,
                         Vc::upcast(this.app_project.rsc_module_context()),
                         Vc::upcast(chunking_context),
@@ -1225,23 +3482,88 @@ impl AppEndpoint {
                     .await?;
                     server_assets.push(manifest);
                     evaluatable_assets.push(loader);
+                    server_action_ids = get_server_action_ids(
+                        Vc::upcast(app_entry.rsc_entry),
+                        app_server_reference_modules,
+                        Vc::upcast(this.app_project.rsc_module_context()),
+                    );
                 }
 
+                evaluatable_assets.extend(
+                    this.app_project
+                        .server_entry_modules()
+                        .await?
+                        .iter()
+                        .copied(),
+                );
+
+                // With a shared base, modules of the server runtime entries are chunked once
+                // for all endpoints instead of being duplicated into every entry chunk.
+                let shared_base = !unsplit
+                    && *this
+                        .app_project
+                        .project()
+                        .next_config()
+                        .shared_server_base()
+                        .await?;
+                let base_availability_info = if shared_base {
+                    let ChunkGroupResult {
+                        assets,
+                        availability_info,
+                    } = *this
+                        .app_project
+                        .server_shared_chunk_group(process_client)
+                        .await?;
+                    server_assets.extend(assets.await?.iter().copied());
+                    availability_info
+                } else {
+                    AvailabilityInfo::Root
+                };
+
+                // The dependencies of the entry can be moved into their own chunk group, so
+                // the entry chunk only contains what isn't available from these chunks.
+                let availability_info = if !unsplit
+                    && *this
+                        .app_project
+                        .project()
+                        .next_config()
+                        .split_server_entry()
+                        .await?
+                {
+                    let dependencies = IncludeModulesModule::new(
+                        app_entry
+                            .rsc_entry
+                            .ident()
+                            .with_modifier(server_entry_dependencies_modifier()),
+                        primary_referenced_modules(app_entry.rsc_entry)
+                            .await?
+                            .clone_value(),
+                    );
+                    let ChunkGroupResult {
+                        assets,
+                        availability_info,
+                    } = *chunking_context
+                        .chunk_group(
+                            dependencies.ident(),
+                            Vc::upcast(dependencies),
+                            Value::new(base_availability_info),
+                        )
+                        .await?;
+                    server_assets.extend(assets.await?.iter().copied());
+                    availability_info
+                } else {
+                    base_availability_info
+                };
+
                 let EntryChunkGroupResult {
                     asset: rsc_chunk, ..
                 } = *{
                     let _span = tracing::trace_span!("server node entrypoint").entered();
                     chunking_context.entry_chunk_group(
-                        server_path.join(
-                            format!(
-                                "app{original_name}.js",
-                                original_name = app_entry.original_name
-                            )
-                            .into(),
-                        ),
-                        app_entry.rsc_entry,
+                        server_path.join(format!("app{manifest_path_prefix}.js").into()),
+                        this.app_project.wrap_server_entry(app_entry.rsc_entry),
                         Vc::cell(evaluatable_assets),
-                        Value::new(AvailabilityInfo::Root),
+                        Value::new(availability_info),
                     )
                 }
                 .await?;
@@ -1249,17 +3571,26 @@ impl AppEndpoint {
 
                 let app_paths_manifest_output = create_app_paths_manifest(
                     node_root,
-                    &app_entry.original_name,
-                    server_path
-                        .await?
-                        .get_path_to(&*rsc_chunk.ident().path().await?)
-                        .context("RSC chunk path should be within app paths manifest directory")?
-                        .into(),
+                    &app_paths_manifest_keys,
+                    &manifest_path_prefix,
+                    get_path_in_root(
+                        &*server_path.await?,
+                        &*rsc_chunk.ident().path().await?,
+                        "RSC chunk",
+                        &app_entry.original_name,
+                    )?
+                    .into(),
+                    &*app_entry.config.await?,
+                    this.page.route_groups(),
                 )?;
                 server_assets.push(app_paths_manifest_output);
 
                 // create react-loadable-manifest for next/dynamic
-                let availability_info = Value::new(AvailabilityInfo::Root);
+                let availability_info = Value::new(if shared_base {
+                    client_base_availability_info.unwrap_or(AvailabilityInfo::Root)
+                } else {
+                    AvailabilityInfo::Root
+                });
                 let mut dynamic_import_modules = collect_next_dynamic_imports(
                     [Vc::upcast(app_entry.rsc_entry)],
                     Vc::upcast(this.app_project.client_module_context()),
@@ -1276,24 +3607,70 @@ impl AppEndpoint {
                     dynamic_import_entries,
                     client_relative_path,
                     node_root.join(
-                        format!(
-                            "server/app{}/react-loadable-manifest.json",
-                            &app_entry.original_name
-                        )
-                        .into(),
+                        format!("server/app{manifest_path_prefix}/react-loadable-manifest.json")
+                            .into(),
                     ),
+                    this.app_project
+                        .project()
+                        .next_config()
+                        .loadable_manifest_format(),
                 );
                 server_assets.extend(loadable_manifest_output.await?.iter().copied());
 
+                if *this
+                    .app_project
+                    .project()
+                    .next_config()
+                    .dynamic_imports_manifest()
+                    .await?
+                {
+                    server_assets.push(create_dynamic_imports_manifest(
+                        dynamic_import_entries,
+                        client_relative_path,
+                        node_root.join(
+                            format!(
+                                "server/app{manifest_path_prefix}/dynamic-imports-manifest.json"
+                            )
+                            .into(),
+                        ),
+                    ));
+                }
+
+                if analyze {
+                    server_assets.push(create_route_stats(
+                        node_root
+                            .join(format!("server/app{manifest_path_prefix}/stats.json").into()),
+                        this.app_project.project().client_root(),
+                        client_assets,
+                        node_root,
+                        Vc::cell(server_assets.clone()),
+                    ));
+                }
+
+                if compact_manifests {
+                    server_assets = compact_json_manifests(server_assets).await?;
+                }
+                if compress_manifests {
+                    server_assets = gzip_manifests(server_assets).await?;
+                }
+
                 AppEndpointOutput::NodeJs {
                     rsc_chunk,
                     server_assets: Vc::cell(server_assets),
                     client_assets,
+                    client_component_ssr_chunks,
+                    server_action_ids,
                 }
             }
         }
         .cell();
 
+        if memory_report.enabled {
+            endpoint_output.output_assets().await?;
+            memory_report.record("server chunks and manifests");
+            memory_report.log(&app_entry.original_name);
+        }
+
         Ok(endpoint_output)
     }
 }
@@ -1326,35 +3703,66 @@ impl Endpoint for AppEndpoint {
         };
         async move {
             let output = self.output().await?;
-            // Must use self.output_assets() instead of output.output_assets() to make it a
-            // single operation
-            let output_assets = self.output_assets();
+            // The paths are collected from the emitted assets, so that the content hashes
+            // match the files on disk when post-processors change their content.
+            let emitted_output_assets = self.emitted_output_assets();
 
             let node_root = this.app_project.project().node_root();
 
             let node_root_ref = &node_root.await?;
+            let client_relative_root = this.app_project.project().client_relative_path();
 
-            this.app_project
+            // Emitting already writes the client and server assets concurrently, as each
+            // asset is written by its own task. The paths only depend on the output assets,
+            // not on the emitted files, so they are collected while the assets are emitted.
+            let (_, server_paths, client_paths) = futures::try_join!(
+                async {
+                    this.app_project
+                        .project()
+                        .emit_all_output_assets(Vc::cell(emitted_output_assets))
+                        .await
+                },
+                async { all_server_paths(emitted_output_assets, node_root).await },
+                async { all_paths_in_root(emitted_output_assets, client_relative_root).await },
+            )?;
+            let server_paths = server_paths.clone_value();
+            let client_paths = client_paths.clone_value();
+
+            // The files are emitted even when the build fails on an issue, so that they can
+            // be inspected.
+            if let Some(threshold) = *this
+                .app_project
                 .project()
-                .emit_all_output_assets(Vc::cell(output_assets))
-                .await?;
-
-            let node_root = this.app_project.project().node_root();
-            let server_paths = all_server_paths(output_assets, node_root)
-                .await?
-                .clone_value();
-
-            let client_relative_root = this.app_project.project().client_relative_path();
-            let client_paths = all_paths_in_root(output_assets, client_relative_root)
+                .next_config()
+                .failing_issue_severity()
                 .await?
-                .clone_value();
+            {
+                let issues = self.output().peek_issues_with_path().await?;
+                let mut failing_issues = 0;
+                for issue in issues.iter() {
+                    if *issue.severity().await? <= threshold {
+                        failing_issues += 1;
+                    }
+                }
+                if failing_issues > 0 {
+                    bail!(
+                        "{} issue(s) of severity {} or above were reported for {}",
+                        failing_issues,
+                        threshold.as_str(),
+                        page_name
+                    );
+                }
+            }
 
             let written_endpoint = match *output {
                 AppEndpointOutput::NodeJs { rsc_chunk, .. } => WrittenEndpoint::NodeJs {
-                    server_entry_path: node_root_ref
-                        .get_path_to(&*rsc_chunk.ident().path().await?)
-                        .context("Node.js chunk entry path must be inside the node root")?
-                        .to_string(),
+                    server_entry_path: get_path_in_root(
+                        node_root_ref,
+                        &*rsc_chunk.ident().path().await?,
+                        "Node.js entry chunk",
+                        &self.route_names().await?.original_name,
+                    )?
+                    .to_string(),
                     server_paths,
                     client_paths,
                 },
@@ -1362,6 +3770,7 @@ impl Endpoint for AppEndpoint {
                     server_paths,
                     client_paths,
                 },
+                AppEndpointOutput::Cancelled => WrittenEndpoint::Cancelled,
             };
             anyhow::Ok(written_endpoint.cell())
         }
@@ -1393,6 +3802,29 @@ impl Endpoint for AppEndpoint {
         let rsc_entry = self.app_endpoint_entry().await?.rsc_entry;
         Ok(Vc::cell(vec![rsc_entry]))
     }
+
+    #[turbo_tasks::function]
+    fn emitted_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.emitted_output_assets()
+    }
+
+    #[turbo_tasks::function]
+    async fn invalidate(self: Vc<Self>) -> Result<Vc<Completion>> {
+        invalidate_endpoint(Vc::upcast(self)).await
+    }
+
+    #[turbo_tasks::function]
+    async fn middleware_manifest(self: Vc<Self>) -> Result<Vc<EndpointMiddlewareManifest>> {
+        Ok(match *self.output().await? {
+            AppEndpointOutput::Edge {
+                middleware_manifest,
+                ..
+            } => middleware_manifest,
+            AppEndpointOutput::NodeJs { .. } | AppEndpointOutput::Cancelled => {
+                EndpointMiddlewareManifest::none()
+            }
+        })
+    }
 }
 
 #[turbo_tasks::value]
@@ -1401,12 +3833,22 @@ enum AppEndpointOutput {
         rsc_chunk: Vc<Box<dyn OutputAsset>>,
         server_assets: Vc<OutputAssets>,
         client_assets: Vc<OutputAssets>,
+        client_component_ssr_chunks: Vc<OutputAssets>,
+        /// The ids of the server actions the endpoint can invoke.
+        server_action_ids: Vc<Vec<RcStr>>,
     },
     Edge {
         files: Vc<OutputAssets>,
+        edge_function_files: Vc<EdgeFunctionFiles>,
         server_assets: Vc<OutputAssets>,
         client_assets: Vc<OutputAssets>,
+        client_component_ssr_chunks: Vc<OutputAssets>,
+        /// The ids of the server actions the endpoint can invoke.
+        server_action_ids: Vc<Vec<RcStr>>,
+        middleware_manifest: Vc<EndpointMiddlewareManifest>,
     },
+    /// The build was cancelled, see [CancellationToken].
+    Cancelled,
 }
 
 #[turbo_tasks::value_impl]
@@ -1429,6 +3871,7 @@ impl AppEndpointOutput {
         match *self {
             AppEndpointOutput::NodeJs { server_assets, .. }
             | AppEndpointOutput::Edge { server_assets, .. } => server_assets,
+            AppEndpointOutput::Cancelled => OutputAssets::empty(),
         }
     }
 
@@ -1437,6 +3880,177 @@ impl AppEndpointOutput {
         match *self {
             AppEndpointOutput::NodeJs { client_assets, .. }
             | AppEndpointOutput::Edge { client_assets, .. } => client_assets,
+            AppEndpointOutput::Cancelled => OutputAssets::empty(),
+        }
+    }
+
+    #[turbo_tasks::function]
+    pub fn server_action_ids(&self) -> Vc<Vec<RcStr>> {
+        match *self {
+            AppEndpointOutput::NodeJs {
+                server_action_ids, ..
+            }
+            | AppEndpointOutput::Edge {
+                server_action_ids, ..
+            } => server_action_ids,
+            AppEndpointOutput::Cancelled => Vc::cell(vec![]),
+        }
+    }
+
+    #[turbo_tasks::function]
+    pub fn client_component_ssr_chunks(&self) -> Vc<OutputAssets> {
+        match *self {
+            AppEndpointOutput::NodeJs {
+                client_component_ssr_chunks,
+                ..
+            }
+            | AppEndpointOutput::Edge {
+                client_component_ssr_chunks,
+                ..
+            } => client_component_ssr_chunks,
+            AppEndpointOutput::Cancelled => OutputAssets::empty(),
+        }
+    }
+
+    /// Lists the output assets that were added, removed or changed between two
+    /// outputs of an endpoint.
+    #[turbo_tasks::function]
+    pub fn diff(old: Vc<Self>, new: Vc<Self>) -> Vc<OutputAssetsDiff> {
+        diff_output_assets(old.output_assets(), new.output_assets())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+
+    use next_core::next_edge::route_regex::get_named_middleware_regex;
+    use turbo_tasks::RcStr;
+
+    use super::{
+        combined_edge_router, page_build_manifests, AppPageEndpointType, PageBuildManifests,
+        COMBINED_EDGE_FUNCTION_NAME,
+    };
+
+    fn manifests(
+        page_type: Option<AppPageEndpointType>,
+        rsc_only: bool,
+        unified: bool,
+    ) -> PageBuildManifests {
+        page_build_manifests(
+            page_type,
+            rsc_only,
+            unified,
+            "/page".into(),
+            vec![
+                "static/chunks/page.js".into(),
+                "static/chunks/main.js".into(),
+            ],
+            vec!["static/chunks/main.js".into()],
+            vec!["static/chunks/polyfills.js".into()],
+        )
+    }
+
+    #[test]
+    fn only_html_endpoint_emits_app_build_manifest() {
+        let html = manifests(Some(AppPageEndpointType::Html), false, false);
+        let app_build_manifest = html.app_build_manifest.unwrap();
+        assert_eq!(
+            app_build_manifest.pages["/page"],
+            ["static/chunks/page.js", "static/chunks/main.js"]
+        );
+
+        let rsc = manifests(Some(AppPageEndpointType::Rsc), false, false);
+        assert!(rsc.app_build_manifest.is_none());
+    }
+
+    #[test]
+    fn rsc_endpoint_emits_app_build_manifest_in_rsc_only_mode() {
+        let rsc = manifests(Some(AppPageEndpointType::Rsc), true, false);
+        assert!(rsc.app_build_manifest.unwrap().pages.contains_key("/page"));
+    }
+
+    #[test]
+    fn every_endpoint_emits_build_manifest() {
+        for page_type in [
+            Some(AppPageEndpointType::Html),
+            Some(AppPageEndpointType::Rsc),
+            None,
+        ] {
+            let build_manifest = manifests(page_type, false, false).build_manifest;
+            assert_eq!(build_manifest.root_main_files, ["static/chunks/main.js"]);
+            assert_eq!(
+                build_manifest.polyfill_files,
+                ["static/chunks/polyfills.js"]
+            );
+            assert!(build_manifest.pages.is_empty());
+        }
+    }
+
+    #[test]
+    fn html_and_rsc_endpoints_emit_identical_unified_build_manifest() {
+        let html = manifests(Some(AppPageEndpointType::Html), false, true);
+        let rsc = manifests(Some(AppPageEndpointType::Rsc), false, true);
+        assert!(html.app_build_manifest.is_none());
+        assert!(rsc.app_build_manifest.is_none());
+        assert!(html.build_manifest.pages.contains_key("/page"));
+        assert_eq!(
+            serde_json::to_string_pretty(&html.build_manifest).unwrap(),
+            serde_json::to_string_pretty(&rsc.build_manifest).unwrap()
+        );
+    }
+
+    #[test]
+    fn html_and_rsc_endpoints_emit_preload_manifests() {
+        for rsc_only in [false, true] {
+            assert!(manifests(Some(AppPageEndpointType::Html), rsc_only, false).preload_manifests);
+            assert!(manifests(Some(AppPageEndpointType::Rsc), rsc_only, false).preload_manifests);
+            assert!(!manifests(None, rsc_only, false).preload_manifests);
         }
     }
+
+    #[test]
+    fn route_endpoint_does_not_emit_app_build_manifest() {
+        assert!(manifests(None, false, false).app_build_manifest.is_none());
+        assert!(manifests(None, true, false).app_build_manifest.is_none());
+    }
+
+    /// Runs the combined edge router in Node.js for a request to `pathname` and
+    /// returns the name of the edge function it was dispatched to.
+    fn route_with_combined_edge_router(pathname: &str) -> String {
+        let routes: [(RcStr, RcStr); 2] = [
+            ("/blog/[slug]", "blog/[slug]/page"),
+            ("/about", "about/page"),
+        ]
+        .map(|(route, name)| (get_named_middleware_regex(route).into(), name.into()));
+        let script = format!(
+            r#"globalThis.self = globalThis;
+self._ENTRIES = {{
+  "middleware_blog/[slug]/page": Promise.resolve({{ default: () => "blog/[slug]/page" }}),
+  "middleware_about/page": Promise.resolve({{ default: () => "about/page" }}),
+}};
+{router}
+self._ENTRIES["middleware_{COMBINED_EDGE_FUNCTION_NAME}"]
+  .then((router) => router.default({{ request: {{ url: "http://localhost{pathname}" }} }}))
+  .then((name) => console.log(name));
+"#,
+            router = combined_edge_router(&routes).unwrap(),
+        );
+        let output = Command::new("node").arg("-e").arg(script).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    #[test]
+    fn combined_edge_router_dispatches_dynamic_route() {
+        assert_eq!(
+            route_with_combined_edge_router("/blog/hello"),
+            "blog/[slug]/page"
+        );
+        assert_eq!(route_with_combined_edge_router("/about"), "about/page");
+    }
 }
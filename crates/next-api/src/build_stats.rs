@@ -0,0 +1,57 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use turbo_tasks_memory::TaskStatistics;
+
+static TASK_STATISTICS: OnceLock<Arc<TaskStatistics>> = OnceLock::new();
+
+/// Makes the task statistics of the [turbo_tasks_memory::MemoryBackend]
+/// building the project available to [build_stats]. They have to be enabled on
+/// the backend, which slows down builds, so this is only called when they were
+/// requested, e.g. with `NEXT_TURBOPACK_TASK_STATISTICS`.
+pub fn register_task_statistics(task_statistics: Arc<TaskStatistics>) {
+    let _ = TASK_STATISTICS.set(task_statistics);
+}
+
+/// How often a turbo-tasks function was called with arguments it was called
+/// with before, i.e. returned a cached result, or with new ones.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FunctionCacheStats {
+    pub cache_hit: u32,
+    pub cache_miss: u32,
+}
+
+/// The cache statistics of the turbo-tasks functions building app routes, e.g.
+/// `AppEndpoint::output` or `AppProject::rsc_module_context`, keyed by their
+/// name. Returns `None` unless task statistics were registered with
+/// [register_task_statistics].
+pub fn build_stats() -> Result<Option<BTreeMap<String, FunctionCacheStats>>> {
+    let Some(task_statistics) = TASK_STATISTICS.get() else {
+        return Ok(None);
+    };
+    let stats: BTreeMap<String, FunctionCacheStats> =
+        serde_json::from_value(serde_json::to_value(&**task_statistics)?)?;
+    Ok(Some(
+        stats
+            .into_iter()
+            .filter_map(|(global_name, stats)| {
+                Some((app_function_name(&global_name)?.to_string(), stats))
+            })
+            .collect(),
+    ))
+}
+
+/// The name of a function of the `app` module, without the crate and module
+/// path, e.g. `AppEndpoint::output`. Global names of turbo-tasks functions
+/// have the form `next-api@<hash>::<module path>::<name>`.
+fn app_function_name(global_name: &str) -> Option<&str> {
+    let (crate_name, path) = global_name.split_once("::")?;
+    if !crate_name.starts_with("next-api@") {
+        return None;
+    }
+    path.trim_start_matches(':').strip_prefix("app::")
+}
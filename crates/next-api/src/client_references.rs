@@ -0,0 +1,340 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use indexmap::{IndexMap, IndexSet};
+use next_core::{
+    next_app::ClientReferencesChunks,
+    next_client_reference::{
+        ClientReferenceGraphResult, ClientReferenceType, ClientReferenceTypes,
+    },
+};
+use turbo_tasks::{ReadRef, TryJoinIterExt, ValueToString, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_browser::ecmascript::EcmascriptDevChunk;
+use turbopack_core::{
+    chunk::ChunkItem,
+    issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::{Module, Modules},
+    reference::primary_referenced_modules,
+};
+
+/// A list of module cycles. Each cycle starts at a client reference module
+/// and lists the modules in import order until the import that leads back to
+/// the client reference.
+#[turbo_tasks::value(transparent)]
+pub struct ModuleCycles(Vec<Vec<Vc<Box<dyn Module>>>>);
+
+/// Finds import cycles that pass through the client module of an ecmascript
+/// client reference.
+///
+/// This walks the client module graph once per client reference, so it is only
+/// meant to run when explicitly enabled.
+#[turbo_tasks::function]
+pub async fn client_reference_cycles(
+    client_references: Vc<ClientReferenceGraphResult>,
+) -> Result<Vc<ModuleCycles>> {
+    let client_modules = client_references
+        .await?
+        .client_references
+        .iter()
+        .filter_map(|r| match r.ty() {
+            ClientReferenceType::EcmascriptClientReference(entry) => Some(entry),
+            ClientReferenceType::CssClientReference(_) => None,
+        })
+        .map(|entry| async move {
+            Vc::upcast::<Box<dyn Module>>(entry.await?.client_module)
+                .resolve()
+                .await
+        })
+        .try_join()
+        .await?;
+
+    let mut cycles = vec![];
+    let mut in_cycle = HashSet::new();
+    for module in client_modules {
+        // A cycle through multiple client references is reported only once.
+        if in_cycle.contains(&module) {
+            continue;
+        }
+        if let Some(cycle) = find_cycle(module).await? {
+            in_cycle.extend(cycle.iter().copied());
+            cycles.push(cycle);
+        }
+    }
+
+    Ok(Vc::cell(cycles))
+}
+
+/// Depth-first search for a path from `root` back to itself.
+async fn find_cycle(root: Vc<Box<dyn Module>>) -> Result<Option<Vec<Vc<Box<dyn Module>>>>> {
+    let mut visited = HashSet::new();
+    visited.insert(root);
+    let mut stack: Vec<(Vc<Box<dyn Module>>, ReadRef<Modules>, usize)> =
+        vec![(root, primary_referenced_modules(root).await?, 0)];
+
+    loop {
+        let Some((_, children, index)) = stack.last_mut() else {
+            return Ok(None);
+        };
+        let Some(&child) = children.get(*index) else {
+            stack.pop();
+            continue;
+        };
+        *index += 1;
+
+        if child == root {
+            return Ok(Some(stack.iter().map(|(module, ..)| *module).collect()));
+        }
+        if visited.insert(child) {
+            let children = primary_referenced_modules(child).await?;
+            stack.push((child, children, 0));
+        }
+    }
+}
+
+/// Modules that are part of more than one chunk, with the number of chunks
+/// they are part of.
+#[turbo_tasks::value(transparent)]
+pub struct DuplicateModules(Vec<(Vc<Box<dyn Module>>, usize)>);
+
+/// Finds modules that are bundled into more than one of the client chunks of
+/// client components.
+///
+/// Only ecmascript chunks are inspected, as CSS is deduplicated by the client
+/// reference manifest.
+#[turbo_tasks::function]
+pub async fn duplicate_client_chunk_modules(
+    client_references_chunks: Vc<ClientReferencesChunks>,
+) -> Result<Vc<DuplicateModules>> {
+    // Chunks of layouts are shared by all client references below them, so they
+    // are deduplicated before counting.
+    let mut chunks = IndexSet::new();
+    for (client_chunks, _) in client_references_chunks
+        .await?
+        .client_component_client_chunks
+        .values()
+    {
+        for &chunk in client_chunks.await?.iter() {
+            chunks.insert(chunk.resolve().await?);
+        }
+    }
+
+    let mut chunk_counts: IndexMap<Vc<Box<dyn Module>>, usize> = IndexMap::new();
+    for chunk in chunks {
+        let Some(chunk) = Vc::try_resolve_downcast_type::<EcmascriptDevChunk>(chunk).await? else {
+            continue;
+        };
+        let content = chunk.chunk().await?.content.await?;
+        let modules = content
+            .chunk_items
+            .iter()
+            .map(|&(chunk_item, _)| chunk_item.module().resolve())
+            .try_join()
+            .await?;
+        for module in modules.into_iter().collect::<IndexSet<_>>() {
+            *chunk_counts.entry(module).or_default() += 1;
+        }
+    }
+
+    Ok(Vc::cell(
+        chunk_counts
+            .into_iter()
+            .filter(|&(_, count)| count > 1)
+            .collect(),
+    ))
+}
+
+/// Returns the layouts in `root_layouts` that are client references
+/// themselves.
+#[turbo_tasks::function]
+pub async fn client_reference_root_layouts(
+    client_references: Vc<ClientReferenceGraphResult>,
+    root_layouts: Vec<Vc<FileSystemPath>>,
+) -> Result<Vc<Vec<Vc<FileSystemPath>>>> {
+    let mut root_layouts_ref = Vec::with_capacity(root_layouts.len());
+    for layout in root_layouts {
+        root_layouts_ref.push((layout, layout.await?));
+    }
+
+    let mut client_root_layouts = vec![];
+    for client_reference in client_references.await?.client_references.iter() {
+        let ClientReferenceType::EcmascriptClientReference(entry) = client_reference.ty() else {
+            continue;
+        };
+        let path = entry.await?.server_ident.path().await?;
+        if let Some((layout, _)) = root_layouts_ref
+            .iter()
+            .find(|(_, layout_ref)| **layout_ref == *path)
+        {
+            if !client_root_layouts.contains(layout) {
+                client_root_layouts.push(*layout);
+            }
+        }
+    }
+
+    Ok(Vc::cell(client_root_layouts))
+}
+
+/// Returns the client references that are one of `error_boundaries`, i.e.
+/// `error`, `global-error` or `not-found` files marked with `"use client"`.
+#[turbo_tasks::function]
+pub async fn client_reference_error_boundaries(
+    client_references: Vc<ClientReferenceGraphResult>,
+    error_boundaries: Vec<Vc<FileSystemPath>>,
+) -> Result<Vc<ClientReferenceTypes>> {
+    let error_boundaries = error_boundaries.into_iter().try_join().await?;
+
+    let mut types = IndexSet::new();
+    for client_reference in client_references.await?.client_references.iter() {
+        let ty = client_reference.ty();
+        let ClientReferenceType::EcmascriptClientReference(entry) = ty else {
+            continue;
+        };
+        let path = entry.await?.server_ident.path().await?;
+        if error_boundaries
+            .iter()
+            .any(|error_boundary| **error_boundary == *path)
+        {
+            types.insert(ty);
+        }
+    }
+
+    Ok(Vc::cell(types))
+}
+
+#[turbo_tasks::value(shared)]
+pub struct RootLayoutClientReferenceIssue {
+    pub path: Vc<FileSystemPath>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for RootLayoutClientReferenceIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Warning.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::AppStructure.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Text("The root layout has a ".into()),
+            StyledString::Code("\"use client\"".into()),
+            StyledString::Text(" directive".into()),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<OptionStyledString>> {
+        Ok(Vc::cell(Some(
+            StyledString::Line(vec![
+                StyledString::Code(self.path.to_string().await?.clone_value()),
+                StyledString::Text(
+                    " is a client component, which renders the entire app on the client. Move the \
+                     directive into the components that need it instead."
+                        .into(),
+                ),
+            ])
+            .cell(),
+        )))
+    }
+}
+
+#[turbo_tasks::value(shared)]
+pub struct ClientReferenceCycleIssue {
+    pub cycle: Vec<Vc<Box<dyn Module>>>,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ClientReferenceCycleIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Analysis.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.cycle[0].ident().path()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text("Circular import between client references".into()).cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<OptionStyledString>> {
+        let mut lines = vec![StyledString::Text(
+            "The following modules import each other in a cycle, which can crash the client \
+             runtime:"
+                .into(),
+        )];
+        for module in self.cycle.iter().chain(self.cycle.first()) {
+            lines.push(StyledString::Code(
+                module.ident().to_string().await?.clone_value(),
+            ));
+        }
+        Ok(Vc::cell(Some(StyledString::Stack(lines).cell())))
+    }
+}
+
+#[turbo_tasks::value(shared)]
+pub struct DuplicateClientModuleIssue {
+    pub module: Vc<Box<dyn Module>>,
+    pub chunk_count: usize,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for DuplicateClientModuleIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Info.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Analysis.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.module.ident().path()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Text("Module is bundled into multiple client chunks".into()).cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<OptionStyledString>> {
+        Ok(Vc::cell(Some(
+            StyledString::Line(vec![
+                StyledString::Code(self.module.ident().to_string().await?.clone_value()),
+                StyledString::Text(
+                    format!(
+                        " is part of {} client chunks, so it may be downloaded more than once.",
+                        self.chunk_count
+                    )
+                    .into(),
+                ),
+            ])
+            .cell(),
+        )))
+    }
+}
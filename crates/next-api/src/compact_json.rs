@@ -0,0 +1,64 @@
+use anyhow::Result;
+use turbo_tasks::{TryJoinIterExt, Vc};
+use turbo_tasks_fs::{json::parse_json_rope_with_source_context, File, FileContent};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    ident::AssetIdent,
+    output::OutputAsset,
+};
+
+/// An [OutputAsset] that emits the JSON content of another output asset
+/// without whitespace, at the same path.
+#[turbo_tasks::value]
+pub struct CompactJsonOutputAsset {
+    asset: Vc<Box<dyn OutputAsset>>,
+}
+
+#[turbo_tasks::value_impl]
+impl CompactJsonOutputAsset {
+    #[turbo_tasks::function]
+    pub fn new(asset: Vc<Box<dyn OutputAsset>>) -> Vc<Self> {
+        CompactJsonOutputAsset { asset }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for CompactJsonOutputAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.asset.ident()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for CompactJsonOutputAsset {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        let FileContent::Content(file) = &*self.asset.content().file_content().await? else {
+            return Ok(AssetContent::file(FileContent::NotFound.cell()));
+        };
+        let json: serde_json::Value = parse_json_rope_with_source_context(file.content())?;
+        Ok(AssetContent::file(
+            File::from(serde_json::to_string(&json)?).into(),
+        ))
+    }
+}
+
+/// Replaces all manifests in `assets` by their compact version. Other assets
+/// are returned unchanged.
+pub(crate) async fn compact_json_manifests(
+    assets: Vec<Vc<Box<dyn OutputAsset>>>,
+) -> Result<Vec<Vc<Box<dyn OutputAsset>>>> {
+    assets
+        .into_iter()
+        .map(|asset| async move {
+            let path = asset.ident().path().await?;
+            Ok(if path.path.ends_with("-manifest.json") {
+                Vc::upcast(CompactJsonOutputAsset::new(asset))
+            } else {
+                asset
+            })
+        })
+        .try_join()
+        .await
+}
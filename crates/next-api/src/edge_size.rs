@@ -0,0 +1,77 @@
+use anyhow::Result;
+use turbo_tasks::{util::FormatBytes, RcStr, TryJoinIterExt, Vc};
+use turbo_tasks_fs::{FileContent, FileSystemPath};
+use turbopack_core::{
+    asset::Asset,
+    issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    output::OutputAsset,
+};
+
+/// The total size in bytes of the content of `assets`.
+pub(crate) async fn total_size(assets: &[Vc<Box<dyn OutputAsset>>]) -> Result<usize> {
+    Ok(assets
+        .iter()
+        .map(|asset| async move {
+            Ok(match &*asset.content().file_content().await? {
+                FileContent::Content(file) => file.content().len(),
+                FileContent::NotFound => 0,
+            })
+        })
+        .try_join()
+        .await?
+        .into_iter()
+        .sum())
+}
+
+/// The files of an edge function exceed the configured
+/// `edgeFunctionSizeLimit`.
+#[turbo_tasks::value(shared)]
+pub struct EdgeFunctionSizeIssue {
+    pub file_path: Vc<FileSystemPath>,
+    pub original_name: RcStr,
+    pub size: usize,
+    pub limit: usize,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for EdgeFunctionSizeIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::CodeGen.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.file_path
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Text("The Edge Function for ".into()),
+            StyledString::Code(self.original_name.clone()),
+            StyledString::Text(" exceeds the size limit".into()),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn description(&self) -> Vc<OptionStyledString> {
+        Vc::cell(Some(
+            StyledString::Text(
+                format!(
+                    "Its files are {} in total, but `edgeFunctionSizeLimit` is {}.",
+                    FormatBytes(self.size),
+                    FormatBytes(self.limit)
+                )
+                .into(),
+            )
+            .cell(),
+        ))
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use anyhow::Result;
+use indexmap::IndexSet;
+use next_core::{all_assets_from_entries, next_manifests::EnvManifest};
+use swc_core::{
+    common::Mark,
+    ecma::{
+        ast::{ComputedPropName, Expr, Lit, MemberExpr, MemberProp},
+        visit::{Visit, VisitWith},
+    },
+};
+use turbo_tasks::{RcStr, TryJoinIterExt, Vc};
+use turbo_tasks_env::EnvMap;
+use turbo_tasks_fs::{File, FileSystemPath};
+use turbopack_browser::ecmascript::EcmascriptDevChunk;
+use turbopack_core::{
+    asset::AssetContent,
+    chunk::ChunkItem,
+    module::Module,
+    output::{OutputAsset, OutputAssets},
+    virtual_output::VirtualOutputAsset,
+};
+use turbopack_ecmascript::{parse::ParseResult, EcmascriptParsable};
+
+const PUBLIC_ENV_PREFIX: &str = "NEXT_PUBLIC_";
+
+/// Creates an `env-manifest.json` at `path`, listing the `NEXT_PUBLIC_`
+/// variables that are inlined into the client chunks in `client_assets`. These
+/// are the `process.env.NEXT_PUBLIC_*` compile-time defines of
+/// `client_define_env` that the bundled modules reference.
+#[turbo_tasks::function]
+pub async fn create_env_manifest(
+    path: Vc<FileSystemPath>,
+    client_assets: Vc<OutputAssets>,
+    client_define_env: Vc<EnvMap>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let public_env: IndexSet<RcStr> = client_define_env
+        .await?
+        .keys()
+        .filter_map(|key| key.strip_prefix("process.env."))
+        .filter(|name| name.starts_with(PUBLIC_ENV_PREFIX))
+        .map(RcStr::from)
+        .collect();
+
+    let mut variables = BTreeSet::new();
+    if !public_env.is_empty() {
+        // The browser chunking context emits every JavaScript chunk as an
+        // `EcmascriptDevChunk`, in development and production builds alike. The
+        // other client chunks, like CSS, can't access the environment.
+        let mut modules = IndexSet::new();
+        for &asset in all_assets_from_entries(client_assets).await?.iter() {
+            let Some(chunk) = Vc::try_resolve_downcast_type::<EcmascriptDevChunk>(asset).await?
+            else {
+                continue;
+            };
+            let content = chunk.chunk().await?.content.await?;
+            for &(chunk_item, _) in content.chunk_items.iter() {
+                modules.insert(chunk_item.module().resolve().await?);
+            }
+        }
+
+        let references = modules.into_iter().map(env_references).try_join().await?;
+        variables.extend(
+            references
+                .iter()
+                .flat_map(|references| references.iter())
+                .filter(|name| public_env.contains(*name))
+                .cloned(),
+        );
+    }
+
+    let manifest = EnvManifest {
+        variables: variables.into_iter().collect(),
+    };
+    Ok(Vc::upcast(VirtualOutputAsset::new(
+        path,
+        AssetContent::file(File::from(serde_json::to_string_pretty(&manifest)?).into()),
+    )))
+}
+
+/// Returns the names of the environment variables `module` reads as
+/// `process.env.NAME` or `process.env["NAME"]`, where `process` is the global
+/// variable.
+#[turbo_tasks::function]
+async fn env_references(module: Vc<Box<dyn Module>>) -> Result<Vc<Vec<RcStr>>> {
+    let Some(module) = Vc::try_resolve_sidecast::<Box<dyn EcmascriptParsable>>(module).await?
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+    let ParseResult::Ok {
+        program,
+        eval_context,
+        ..
+    } = &*module.failsafe_parse().await?
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let mut visitor = EnvReferencesVisitor {
+        unresolved_mark: eval_context.unresolved_mark(),
+        names: vec![],
+    };
+    program.visit_with(&mut visitor);
+    Ok(Vc::cell(visitor.names))
+}
+
+struct EnvReferencesVisitor {
+    unresolved_mark: Mark,
+    names: Vec<RcStr>,
+}
+
+impl EnvReferencesVisitor {
+    /// Whether `expr` is `process.env` with the global `process`.
+    fn is_process_env(&self, expr: &Expr) -> bool {
+        let Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(env),
+            ..
+        }) = expr
+        else {
+            return false;
+        };
+        env.sym == "env"
+            && matches!(
+                &**obj,
+                Expr::Ident(process)
+                    if process.sym == "process" && process.ctxt.outer() == self.unresolved_mark
+            )
+    }
+}
+
+impl Visit for EnvReferencesVisitor {
+    fn visit_member_expr(&mut self, expr: &MemberExpr) {
+        if self.is_process_env(&expr.obj) {
+            match &expr.prop {
+                MemberProp::Ident(name) => self.names.push(name.sym.as_str().into()),
+                MemberProp::Computed(ComputedPropName { expr, .. }) => {
+                    if let Expr::Lit(Lit::Str(name)) = &**expr {
+                        self.names.push(name.value.as_str().into());
+                    }
+                }
+                _ => {}
+            }
+        }
+        expr.visit_children_with(self);
+    }
+}
@@ -10,6 +10,19 @@ use turbopack_core::{
 
 use crate::paths::get_font_paths_from_root;
 
+/// Returns the paths of all font files referenced from `client_assets`,
+/// relative to `client_root`.
+#[turbo_tasks::function]
+pub(crate) async fn used_font_paths(
+    client_root: Vc<FileSystemPath>,
+    client_assets: Vc<OutputAssets>,
+) -> Result<Vc<Vec<RcStr>>> {
+    let all_client_output_assets = all_assets_from_entries(client_assets).await?;
+    Ok(Vc::cell(
+        get_font_paths_from_root(&*client_root.await?, &all_client_output_assets).await?,
+    ))
+}
+
 pub(crate) async fn create_font_manifest(
     client_root: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
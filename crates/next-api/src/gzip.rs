@@ -0,0 +1,82 @@
+use std::io::Write;
+
+use anyhow::Result;
+use flate2::{write::GzEncoder, Compression};
+use turbo_tasks::{TryJoinIterExt, Vc};
+use turbo_tasks_fs::{File, FileContent};
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    ident::AssetIdent,
+    output::OutputAsset,
+};
+
+/// An [OutputAsset] that emits the gzip-compressed content of another output
+/// asset, next to it with a `.gz` extension appended.
+#[turbo_tasks::value]
+pub struct GzipOutputAsset {
+    asset: Vc<Box<dyn OutputAsset>>,
+}
+
+#[turbo_tasks::value_impl]
+impl GzipOutputAsset {
+    #[turbo_tasks::function]
+    pub fn new(asset: Vc<Box<dyn OutputAsset>>) -> Vc<Self> {
+        GzipOutputAsset { asset }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for GzipOutputAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        AssetIdent::from_path(self.asset.ident().path().append(".gz".into()))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for GzipOutputAsset {
+    #[turbo_tasks::function]
+    async fn content(&self) -> Result<Vc<AssetContent>> {
+        let FileContent::Content(file) = &*self.asset.content().file_content().await? else {
+            return Ok(AssetContent::file(FileContent::NotFound.cell()));
+        };
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&file.content().to_bytes()?)?;
+        Ok(AssetContent::file(File::from(encoder.finish()?).into()))
+    }
+}
+
+/// The manifests of an endpoint that are compressed when `compressManifests`
+/// is enabled. The Next.js runtime reads the `.json.gz` file of a manifest
+/// when its `.json` file is missing.
+pub(crate) const COMPRESSED_MANIFESTS: &[&str] = &[
+    "app-paths-manifest.json",
+    "app-revalidate-manifest.json",
+    "css-preload-manifest.json",
+    "dynamic-imports-manifest.json",
+    "env-manifest.json",
+    "error-preload-manifest.json",
+    "headers-manifest.json",
+    "legacy-middleware-manifest.json",
+    "middleware-manifest.json",
+    "sources-manifest.json",
+];
+
+/// Replaces the [COMPRESSED_MANIFESTS] in `assets` by their gzip-compressed
+/// version. Other assets are returned unchanged.
+pub(crate) async fn gzip_manifests(
+    assets: Vec<Vc<Box<dyn OutputAsset>>>,
+) -> Result<Vec<Vc<Box<dyn OutputAsset>>>> {
+    assets
+        .into_iter()
+        .map(|asset| async move {
+            let path = asset.ident().path().await?;
+            Ok(if COMPRESSED_MANIFESTS.contains(&path.file_name()) {
+                Vc::upcast(GzipOutputAsset::new(asset))
+            } else {
+                asset
+            })
+        })
+        .try_join()
+        .await
+}
@@ -28,7 +28,10 @@ use crate::{
         all_server_paths, get_js_paths_from_root, get_wasm_paths_from_root, wasm_paths_to_bindings,
     },
     project::Project,
-    route::{Endpoint, WrittenEndpoint},
+    route::{
+        endpoint_revision, invalidate_endpoint, Endpoint, EndpointMiddlewareManifest,
+        WrittenEndpoint,
+    },
 };
 
 #[turbo_tasks::value]
@@ -163,38 +166,18 @@ impl InstrumentationEndpoint {
 
     #[turbo_tasks::function]
     async fn output_assets(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        endpoint_revision(Vc::upcast(self)).await?.track();
         let this = self.await?;
 
         if this.is_edge {
-            let edge_files = self.edge_files();
-            let mut output_assets = edge_files.await?.clone_value();
-
-            let node_root = this.project.node_root();
-            let node_root_value = node_root.await?;
-
-            let file_paths_from_root =
-                get_js_paths_from_root(&node_root_value, &output_assets).await?;
-
-            let all_output_assets = all_assets_from_entries(edge_files).await?;
-
-            let wasm_paths_from_root =
-                get_wasm_paths_from_root(&node_root_value, &all_output_assets).await?;
-
-            let instrumentation_definition = InstrumentationDefinition {
-                files: file_paths_from_root,
-                wasm: wasm_paths_to_bindings(wasm_paths_from_root),
-                name: "instrumentation".into(),
-                ..Default::default()
-            };
-            let middleware_manifest_v2 = MiddlewaresManifestV2 {
-                instrumentation: Some(instrumentation_definition),
-                ..Default::default()
-            };
+            let mut output_assets = self.edge_files().await?.clone_value();
             let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
-                node_root.join("server/instrumentation/middleware-manifest.json".into()),
+                this.project
+                    .node_root()
+                    .join("server/instrumentation/middleware-manifest.json".into()),
                 AssetContent::file(
                     FileContent::Content(File::from(serde_json::to_string_pretty(
-                        &middleware_manifest_v2,
+                        &self.middleware_manifest().await?.manifest,
                     )?))
                     .cell(),
                 ),
@@ -260,4 +243,48 @@ impl Endpoint for InstrumentationEndpoint {
             core_modules.edge_entry_module,
         ]))
     }
+
+    #[turbo_tasks::function]
+    fn emitted_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output_assets()
+    }
+
+    #[turbo_tasks::function]
+    async fn invalidate(self: Vc<Self>) -> Result<Vc<Completion>> {
+        invalidate_endpoint(Vc::upcast(self)).await
+    }
+
+    #[turbo_tasks::function]
+    async fn middleware_manifest(self: Vc<Self>) -> Result<Vc<EndpointMiddlewareManifest>> {
+        let this = self.await?;
+        if !this.is_edge {
+            return Ok(EndpointMiddlewareManifest::none());
+        }
+
+        let edge_files = self.edge_files();
+        let node_root_value = this.project.node_root().await?;
+
+        let file_paths_from_root =
+            get_js_paths_from_root(&node_root_value, &edge_files.await?).await?;
+
+        let all_output_assets = all_assets_from_entries(edge_files).await?;
+
+        let wasm_paths_from_root =
+            get_wasm_paths_from_root(&node_root_value, &all_output_assets).await?;
+
+        let instrumentation_definition = InstrumentationDefinition {
+            files: file_paths_from_root,
+            wasm: wasm_paths_to_bindings(wasm_paths_from_root),
+            name: "instrumentation".into(),
+            ..Default::default()
+        };
+        let middleware_manifest_v2 = MiddlewaresManifestV2 {
+            instrumentation: Some(instrumentation_definition),
+            ..Default::default()
+        };
+        Ok(EndpointMiddlewareManifest {
+            manifest: Some(middleware_manifest_v2),
+        }
+        .cell())
+    }
 }
@@ -3,18 +3,28 @@
 #![feature(impl_trait_in_assoc_type)]
 
 mod app;
+pub mod build_stats;
+mod client_references;
+mod compact_json;
 mod dynamic_imports;
+mod edge_size;
 pub mod entrypoints;
+mod env_manifest;
 mod font;
 pub mod global_module_id_strategy;
+mod gzip;
 mod instrumentation;
 mod loadable_manifest;
 mod middleware;
+mod node_builtins;
+mod openapi;
 mod pages;
 pub mod paths;
+pub mod post_process;
 pub mod project;
 pub mod route;
 mod server_actions;
+mod stats;
 mod versioned_content_map;
 
 // Declare build-time information variables generated in build.rs
@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
-use next_core::next_manifests::LoadableManifest;
+use indexmap::IndexMap;
+use next_core::{
+    next_config::LoadableManifestFormat,
+    next_manifests::{LoadableManifest, StreamingLoadableManifest},
+};
 use turbo_tasks::{RcStr, TryFlatJoinIterExt, Vc};
 use turbo_tasks_fs::{File, FileContent, FileSystemPath};
 use turbopack_core::{
@@ -18,11 +22,16 @@ pub async fn create_react_loadable_manifest(
     dynamic_import_entries: Vc<DynamicImportedChunks>,
     client_relative_path: Vc<FileSystemPath>,
     output_path: Vc<FileSystemPath>,
+    format: Vc<LoadableManifestFormat>,
 ) -> Result<Vc<OutputAssets>> {
     let dynamic_import_entries = &*dynamic_import_entries.await?;
 
     let mut output = vec![];
     let mut loadable_manifest: HashMap<RcStr, LoadableManifest> = Default::default();
+    let mut streaming_loadable_manifest = StreamingLoadableManifest {
+        version: 2,
+        ..Default::default()
+    };
 
     for (origin, dynamic_imports) in dynamic_import_entries.into_iter() {
         let origin_path = &*origin.ident().path().await?;
@@ -47,6 +56,12 @@ pub async fn create_react_loadable_manifest(
                 .try_flat_join()
                 .await?;
 
+            streaming_loadable_manifest
+                .modules
+                .entry(origin_path.to_string().into())
+                .or_default()
+                .insert(import.clone(), files.clone());
+
             let manifest_item = LoadableManifest {
                 id: id.clone(),
                 files,
@@ -56,16 +71,63 @@ pub async fn create_react_loadable_manifest(
         }
     }
 
+    let content = match *format.await? {
+        LoadableManifestFormat::Classic => serde_json::to_string_pretty(&loadable_manifest)?,
+        LoadableManifestFormat::Streaming => {
+            serde_json::to_string_pretty(&streaming_loadable_manifest)?
+        }
+    };
     let loadable_manifest = Vc::upcast(VirtualOutputAsset::new(
+        output_path,
+        AssetContent::file(FileContent::Content(File::from(content)).cell()),
+    ));
+
+    output.push(loadable_manifest);
+    Ok(Vc::cell(output))
+}
+
+/// Creates a manifest mapping the id of each dynamic import to the client
+/// chunk paths it loads. Unlike the react-loadable manifest, the chunks are not
+/// added to the output; it is only meant for preloading heuristics.
+#[turbo_tasks::function]
+pub async fn create_dynamic_imports_manifest(
+    dynamic_import_entries: Vc<DynamicImportedChunks>,
+    client_relative_path: Vc<FileSystemPath>,
+    output_path: Vc<FileSystemPath>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let dynamic_import_entries = &*dynamic_import_entries.await?;
+    let client_relative_path = &*client_relative_path.await?;
+
+    let mut dynamic_imports_manifest: IndexMap<RcStr, Vec<RcStr>> = IndexMap::new();
+
+    for (origin, dynamic_imports) in dynamic_import_entries.iter() {
+        let origin_path = &*origin.ident().path().await?;
+
+        for (import, chunk_output) in dynamic_imports {
+            let id: RcStr = format!("{} -> {}", origin_path, import).into();
+
+            let files = chunk_output
+                .await?
+                .iter()
+                .map(|&file| async move {
+                    Ok(client_relative_path
+                        .get_path_to(&*file.ident().path().await?)
+                        .map(|path| path.into()))
+                })
+                .try_flat_join()
+                .await?;
+
+            dynamic_imports_manifest.insert(id, files);
+        }
+    }
+
+    Ok(Vc::upcast(VirtualOutputAsset::new(
         output_path,
         AssetContent::file(
             FileContent::Content(File::from(serde_json::to_string_pretty(
-                &loadable_manifest,
+                &dynamic_imports_manifest,
             )?))
             .cell(),
         ),
-    ));
-
-    output.push(loadable_manifest);
-    Ok(Vc::cell(output))
+    )))
 }
@@ -28,7 +28,10 @@ use crate::{
         get_wasm_paths_from_root, paths_to_bindings, wasm_paths_to_bindings,
     },
     project::Project,
-    route::{Endpoint, WrittenEndpoint},
+    route::{
+        endpoint_revision, invalidate_endpoint, Endpoint, EndpointMiddlewareManifest,
+        WrittenEndpoint,
+    },
 };
 
 #[turbo_tasks::value]
@@ -119,6 +122,101 @@ impl MiddlewareEndpoint {
 
     #[turbo_tasks::function]
     async fn output_assets(self: Vc<Self>) -> Result<Vc<OutputAssets>> {
+        endpoint_revision(Vc::upcast(self)).await?.track();
+        let this = self.await?;
+
+        let mut output_assets = self.edge_files().await?.clone_value();
+        let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
+            this.project
+                .node_root()
+                .join("server/middleware/middleware-manifest.json".into()),
+            AssetContent::file(
+                FileContent::Content(File::from(serde_json::to_string_pretty(
+                    &self.middleware_manifest().await?.manifest,
+                )?))
+                .cell(),
+            ),
+        ));
+        output_assets.push(middleware_manifest_v2);
+
+        Ok(Vc::cell(output_assets))
+    }
+
+    #[turbo_tasks::function]
+    async fn userland_module(self: Vc<Self>) -> Result<Vc<Box<dyn Module>>> {
+        let this = self.await?;
+
+        Ok(this
+            .asset_context
+            .process(
+                this.source,
+                Value::new(ReferenceType::Entry(EntryReferenceSubType::Middleware)),
+            )
+            .module())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Endpoint for MiddlewareEndpoint {
+    #[turbo_tasks::function]
+    async fn write_to_disk(self: Vc<Self>) -> Result<Vc<WrittenEndpoint>> {
+        let span = tracing::info_span!("middleware endpoint");
+        async move {
+            let this = self.await?;
+            let output_assets = self.output_assets();
+            let _ = output_assets.resolve().await?;
+            this.project
+                .emit_all_output_assets(Vc::cell(output_assets))
+                .await?;
+
+            let node_root = this.project.node_root();
+            let server_paths = all_server_paths(output_assets, node_root)
+                .await?
+                .clone_value();
+
+            // Middleware could in theory have a client path (e.g. `new URL`).
+            let client_relative_root = this.project.client_relative_path();
+            let client_paths = all_paths_in_root(output_assets, client_relative_root)
+                .await?
+                .clone_value();
+
+            Ok(WrittenEndpoint::Edge {
+                server_paths,
+                client_paths,
+            }
+            .cell())
+        }
+        .instrument(span)
+        .await
+    }
+
+    #[turbo_tasks::function]
+    async fn server_changed(self: Vc<Self>) -> Result<Vc<Completion>> {
+        Ok(self.await?.project.server_changed(self.output_assets()))
+    }
+
+    #[turbo_tasks::function]
+    fn client_changed(self: Vc<Self>) -> Vc<Completion> {
+        Completion::immutable()
+    }
+
+    #[turbo_tasks::function]
+    fn root_modules(self: Vc<Self>) -> Result<Vc<Modules>> {
+        Ok(Vc::cell(vec![self.userland_module()]))
+    }
+
+    #[turbo_tasks::function]
+    fn emitted_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output_assets()
+    }
+
+    #[turbo_tasks::function]
+    async fn invalidate(self: Vc<Self>) -> Result<Vc<Completion>> {
+        invalidate_endpoint(Vc::upcast(self)).await
+    }
+
+    #[turbo_tasks::function]
+    async fn middleware_manifest(self: Vc<Self>) -> Result<Vc<EndpointMiddlewareManifest>> {
         let this = self.await?;
 
         let userland_module = self.userland_module();
@@ -126,10 +224,9 @@ impl MiddlewareEndpoint {
         let config = parse_config_from_source(userland_module);
 
         let edge_files = self.edge_files();
-        let mut output_assets = edge_files.await?.clone_value();
+        let output_assets = edge_files.await?;
 
-        let node_root = this.project.node_root();
-        let node_root_value = node_root.await?;
+        let node_root_value = this.project.node_root().await?;
 
         let file_paths_from_root = get_js_paths_from_root(&node_root_value, &output_assets).await?;
 
@@ -240,80 +337,9 @@ impl MiddlewareEndpoint {
                 .collect(),
             ..Default::default()
         };
-        let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
-            node_root.join("server/middleware/middleware-manifest.json".into()),
-            AssetContent::file(
-                FileContent::Content(File::from(serde_json::to_string_pretty(
-                    &middleware_manifest_v2,
-                )?))
-                .cell(),
-            ),
-        ));
-        output_assets.push(middleware_manifest_v2);
-
-        Ok(Vc::cell(output_assets))
-    }
-
-    #[turbo_tasks::function]
-    async fn userland_module(self: Vc<Self>) -> Result<Vc<Box<dyn Module>>> {
-        let this = self.await?;
-
-        Ok(this
-            .asset_context
-            .process(
-                this.source,
-                Value::new(ReferenceType::Entry(EntryReferenceSubType::Middleware)),
-            )
-            .module())
-    }
-}
-
-#[turbo_tasks::value_impl]
-impl Endpoint for MiddlewareEndpoint {
-    #[turbo_tasks::function]
-    async fn write_to_disk(self: Vc<Self>) -> Result<Vc<WrittenEndpoint>> {
-        let span = tracing::info_span!("middleware endpoint");
-        async move {
-            let this = self.await?;
-            let output_assets = self.output_assets();
-            let _ = output_assets.resolve().await?;
-            this.project
-                .emit_all_output_assets(Vc::cell(output_assets))
-                .await?;
-
-            let node_root = this.project.node_root();
-            let server_paths = all_server_paths(output_assets, node_root)
-                .await?
-                .clone_value();
-
-            // Middleware could in theory have a client path (e.g. `new URL`).
-            let client_relative_root = this.project.client_relative_path();
-            let client_paths = all_paths_in_root(output_assets, client_relative_root)
-                .await?
-                .clone_value();
-
-            Ok(WrittenEndpoint::Edge {
-                server_paths,
-                client_paths,
-            }
-            .cell())
+        Ok(EndpointMiddlewareManifest {
+            manifest: Some(middleware_manifest_v2),
         }
-        .instrument(span)
-        .await
-    }
-
-    #[turbo_tasks::function]
-    async fn server_changed(self: Vc<Self>) -> Result<Vc<Completion>> {
-        Ok(self.await?.project.server_changed(self.output_assets()))
-    }
-
-    #[turbo_tasks::function]
-    fn client_changed(self: Vc<Self>) -> Vc<Completion> {
-        Completion::immutable()
-    }
-
-    #[turbo_tasks::function]
-    fn root_modules(self: Vc<Self>) -> Result<Vc<Modules>> {
-        Ok(Vc::cell(vec![self.userland_module()]))
+        .cell())
     }
 }
@@ -0,0 +1,95 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::Result;
+use next_core::next_edge::unsupported::get_unsupported_module_name;
+use turbo_tasks::{RcStr, ValueToString, Vc};
+use turbo_tasks_fs::FileSystemPath;
+use turbopack_core::{
+    issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString},
+    module::Module,
+    reference::primary_referenced_modules,
+};
+
+/// An import of a Node.js built-in module, together with the module importing
+/// it.
+#[turbo_tasks::value(transparent)]
+pub struct NodeBuiltinImports(Vec<(Vc<Box<dyn Module>>, RcStr)>);
+
+/// Finds all imports of Node.js built-in modules that are reachable from
+/// `entry`.
+///
+/// In the edge runtime, these imports are replaced by modules throwing at
+/// runtime, so this only finds imports in module graphs resolved with the edge
+/// resolve options.
+#[turbo_tasks::function]
+pub async fn edge_node_builtin_imports(
+    entry: Vc<Box<dyn Module>>,
+) -> Result<Vc<NodeBuiltinImports>> {
+    let mut imports = vec![];
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(entry);
+    queue.push_back(entry);
+
+    while let Some(module) = queue.pop_front() {
+        for &referenced in primary_referenced_modules(module).await?.iter() {
+            if let Some(builtin) = get_unsupported_module_name(referenced).await? {
+                imports.push((module, builtin));
+            } else if visited.insert(referenced) {
+                queue.push_back(referenced);
+            }
+        }
+    }
+
+    Ok(Vc::cell(imports))
+}
+
+#[turbo_tasks::value(shared)]
+pub struct EdgeNodeBuiltinImportIssue {
+    pub importer: Vc<Box<dyn Module>>,
+    pub builtin: RcStr,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for EdgeNodeBuiltinImportIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        IssueSeverity::Error.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::Resolve.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.importer.ident().path()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> Vc<StyledString> {
+        StyledString::Line(vec![
+            StyledString::Text("The Node.js module ".into()),
+            StyledString::Code(self.builtin.clone()),
+            StyledString::Text(" is not supported in the Edge Runtime".into()),
+        ])
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<OptionStyledString>> {
+        Ok(Vc::cell(Some(
+            StyledString::Line(vec![
+                StyledString::Text("It is imported by ".into()),
+                StyledString::Code(self.importer.ident().to_string().await?.clone_value()),
+                StyledString::Text(
+                    ", which is part of a route using the Edge Runtime. The import will throw \
+                     when it is evaluated."
+                        .into(),
+                ),
+            ])
+            .cell(),
+        )))
+    }
+}
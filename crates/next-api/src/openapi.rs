@@ -0,0 +1,74 @@
+use anyhow::Result;
+use indexmap::IndexMap;
+use next_core::{
+    next_app::{app_route_methods::AppRouteMethods, AppPage, AppPath, PathSegment},
+    next_manifests::{OpenApiOperation, OpenApiParameter, OpenApiResponse, OpenApiStub},
+};
+use turbo_tasks::{RcStr, Vc};
+use turbo_tasks_fs::{File, FileSystemPath};
+use turbopack_core::{
+    asset::AssetContent, output::OutputAsset, virtual_output::VirtualOutputAsset,
+};
+
+/// Creates `server/app{manifest_path_prefix}/openapi.json`, an OpenAPI stub
+/// listing the HTTP methods a route handler exports.
+#[turbo_tasks::function]
+pub async fn create_openapi_stub(
+    node_root: Vc<FileSystemPath>,
+    manifest_path_prefix: RcStr,
+    page: AppPage,
+    methods: Vc<AppRouteMethods>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let mut path_template = String::new();
+    let mut parameters = vec![];
+    for segment in AppPath::from(page.clone()).iter() {
+        path_template.push('/');
+        match segment {
+            PathSegment::Static(segment) => path_template.push_str(segment),
+            PathSegment::Dynamic(name)
+            | PathSegment::CatchAll(name)
+            | PathSegment::OptionalCatchAll(name) => {
+                path_template.push('{');
+                path_template.push_str(name);
+                path_template.push('}');
+                parameters.push(name.clone());
+            }
+        }
+    }
+    if path_template.is_empty() {
+        path_template.push('/');
+    }
+
+    let operations = methods
+        .await?
+        .iter()
+        .map(|method| {
+            let operation = OpenApiOperation {
+                // OpenAPI path parameters are always required, even for optional catch-all
+                // segments.
+                parameters: parameters
+                    .iter()
+                    .map(|name| OpenApiParameter {
+                        name: name.clone(),
+                        location: "path",
+                        required: true,
+                    })
+                    .collect(),
+                responses: [("default".into(), OpenApiResponse::default())]
+                    .into_iter()
+                    .collect(),
+            };
+            (method.to_lowercase().into(), operation)
+        })
+        .collect::<IndexMap<RcStr, _>>();
+
+    let stub = OpenApiStub {
+        openapi: "3.0.3",
+        paths: [(path_template.into(), operations)].into_iter().collect(),
+    };
+
+    Ok(Vc::upcast(VirtualOutputAsset::new(
+        node_root.join(format!("server/app{manifest_path_prefix}/openapi.json").into()),
+        AssetContent::file(File::from(serde_json::to_string_pretty(&stub)?).into()),
+    )))
+}
@@ -10,7 +10,7 @@ use next_core::{
         get_client_runtime_entries, ClientContextType, RuntimeEntries,
     },
     next_dynamic::NextDynamicTransition,
-    next_edge::route_regex::get_named_middleware_regex,
+    next_edge::route_regex::{get_named_middleware_regex, with_base_path},
     next_manifests::{
         BuildManifest, EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2,
         PagesManifest,
@@ -70,7 +70,10 @@ use crate::{
         get_wasm_paths_from_root, paths_to_bindings, wasm_paths_to_bindings,
     },
     project::Project,
-    route::{Endpoint, Route, Routes, WrittenEndpoint},
+    route::{
+        endpoint_revision, invalidate_endpoint, EdgeFunctionMatcher, EdgeFunctionMatchers,
+        Endpoint, EndpointMiddlewareManifest, Route, Routes, WrittenEndpoint,
+    },
 };
 
 #[turbo_tasks::value]
@@ -184,6 +187,39 @@ impl PagesProject {
         Ok(Vc::cell(routes))
     }
 
+    /// Returns the names and matchers of all pages and API routes that use the
+    /// edge runtime, sorted by name. Only the SSR entry modules of the routes
+    /// are created, their endpoints aren't built.
+    #[turbo_tasks::function]
+    pub async fn edge_functions(self: Vc<Self>) -> Result<Vc<EdgeFunctionMatchers>> {
+        let endpoints = self
+            .routes()
+            .await?
+            .values()
+            .filter_map(|route| match route {
+                Route::Page { html_endpoint, .. } => Some(*html_endpoint),
+                Route::PageApi { endpoint } => Some(*endpoint),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut functions = Vec::new();
+        for endpoint in endpoints {
+            let Some(endpoint) = Vc::try_resolve_downcast_type::<PageEndpoint>(endpoint).await?
+            else {
+                continue;
+            };
+            if !matches!(
+                endpoint.internal_ssr_chunk_module().await?.runtime,
+                NextRuntime::Edge
+            ) {
+                continue;
+            }
+            functions.push(endpoint.edge_function_matcher().await?.clone_value());
+        }
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Vc::cell(functions))
+    }
+
     #[turbo_tasks::function]
     async fn to_endpoint(
         self: Vc<Self>,
@@ -645,6 +681,24 @@ impl PageEndpoint {
         Ok(Vc::upcast(FileSource::new(this.page.project_path())))
     }
 
+    /// The name and matcher the endpoint has in the middleware manifest when
+    /// it uses the edge runtime.
+    #[turbo_tasks::function]
+    async fn edge_function_matcher(self: Vc<Self>) -> Result<Vc<EdgeFunctionMatcher>> {
+        let this = self.await?;
+        let base_path = this
+            .pages_project
+            .project()
+            .next_config()
+            .base_path()
+            .await?;
+        Ok(page_edge_function_matcher(
+            base_path.as_deref().unwrap_or_default(),
+            this.pathname.await?.clone_value(),
+        )
+        .cell())
+    }
+
     #[turbo_tasks::function]
     async fn client_module(self: Vc<Self>) -> Result<Vc<Box<dyn Module>>> {
         let this = self.await?;
@@ -986,6 +1040,10 @@ impl PageEndpoint {
             node_root.join(
                 format!("server/pages{loadable_path_prefix}/react-loadable-manifest.json").into(),
             ),
+            self.pages_project
+                .project()
+                .next_config()
+                .loadable_manifest_format(),
         ))
     }
 
@@ -1037,6 +1095,7 @@ impl PageEndpoint {
 
     #[turbo_tasks::function]
     async fn output(self: Vc<Self>) -> Result<Vc<PageEndpointOutput>> {
+        endpoint_revision(Vc::upcast(self)).await?.track();
         let this = self.await?;
 
         let mut server_assets = vec![];
@@ -1098,6 +1157,12 @@ impl PageEndpoint {
                 dynamic_import_entries,
             } => {
                 let node_root = this.pages_project.project().node_root();
+                let server_actions_manifest_name = this
+                    .pages_project
+                    .project()
+                    .next_config()
+                    .server_actions_manifest_name()
+                    .await?;
                 let files_value = files.await?;
                 if let Some(&file) = files_value.first() {
                     let pages_manifest = self.pages_manifest(file);
@@ -1110,7 +1175,7 @@ impl PageEndpoint {
                 //
                 // they are created in `setup-dev-bundler.ts`
                 let mut file_paths_from_root = vec![
-                    "server/server-reference-manifest.js".into(),
+                    format!("server/{server_actions_manifest_name}.js").into(),
                     "server/middleware-build-manifest.js".into(),
                     "server/middleware-react-loadable-manifest.js".into(),
                     "server/next-font-manifest.js".into(),
@@ -1131,10 +1196,14 @@ impl PageEndpoint {
                     get_paths_from_root(&node_root_value, &all_output_assets, |_asset| true)
                         .await?;
 
-                let named_regex = get_named_middleware_regex(&pathname).into();
+                let EdgeFunctionMatcher {
+                    name,
+                    original_source,
+                    regex,
+                } = self.edge_function_matcher().await?.clone_value();
                 let matchers = MiddlewareMatcher {
-                    regexp: Some(named_regex),
-                    original_source: pathname.clone_value(),
+                    regexp: Some(regex),
+                    original_source,
                     ..Default::default()
                 };
                 let original_name = this.original_name.await?;
@@ -1142,7 +1211,7 @@ impl PageEndpoint {
                     files: file_paths_from_root,
                     wasm: wasm_paths_to_bindings(wasm_paths_from_root),
                     assets: paths_to_bindings(all_assets),
-                    name: pathname.clone_value(),
+                    name,
                     page: original_name.clone_value(),
                     regions: None,
                     matchers: vec![matchers],
@@ -1156,7 +1225,7 @@ impl PageEndpoint {
                     ..Default::default()
                 };
                 let manifest_path_prefix = get_asset_prefix_from_pathname(&this.pathname.await?);
-                let middleware_manifest_v2 = Vc::upcast(VirtualOutputAsset::new(
+                server_assets.push(Vc::upcast(VirtualOutputAsset::new(
                     node_root.join(
                         format!("server/pages{manifest_path_prefix}/middleware-manifest.json")
                             .into(),
@@ -1167,8 +1236,7 @@ impl PageEndpoint {
                         )?))
                         .cell(),
                     ),
-                ));
-                server_assets.push(middleware_manifest_v2);
+                )));
 
                 let loadable_manifest_output = self.react_loadable_manifest(dynamic_import_entries);
                 server_assets.extend(loadable_manifest_output.await?.iter().copied());
@@ -1177,6 +1245,10 @@ impl PageEndpoint {
                     files,
                     server_assets: Vc::cell(server_assets),
                     client_assets,
+                    middleware_manifest: EndpointMiddlewareManifest {
+                        manifest: Some(middleware_manifest_v2),
+                    }
+                    .cell(),
                 }
             }
         };
@@ -1230,20 +1302,22 @@ impl Endpoint for PageEndpoint {
             // single operation
             let output_assets = self.output_assets();
 
-            this.pages_project
-                .project()
-                .emit_all_output_assets(Vc::cell(output_assets))
-                .await?;
-
             let node_root = this.pages_project.project().node_root();
-            let server_paths = all_server_paths(output_assets, node_root)
-                .await?
-                .clone_value();
-
             let client_relative_root = this.pages_project.project().client_relative_path();
-            let client_paths = all_paths_in_root(output_assets, client_relative_root)
-                .await?
-                .clone_value();
+
+            // See `AppEndpoint::write_to_disk`.
+            let (_, server_paths, client_paths) = futures::try_join!(
+                async {
+                    this.pages_project
+                        .project()
+                        .emit_all_output_assets(Vc::cell(output_assets))
+                        .await
+                },
+                async { all_server_paths(output_assets, node_root).await },
+                async { all_paths_in_root(output_assets, client_relative_root).await },
+            )?;
+            let server_paths = server_paths.clone_value();
+            let client_paths = client_paths.clone_value();
 
             let node_root = &node_root.await?;
             let written_endpoint = match *output {
@@ -1300,6 +1374,27 @@ impl Endpoint for PageEndpoint {
 
         Ok(Vc::cell(modules))
     }
+
+    #[turbo_tasks::function]
+    fn emitted_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+        self.output_assets()
+    }
+
+    #[turbo_tasks::function]
+    async fn invalidate(self: Vc<Self>) -> Result<Vc<Completion>> {
+        invalidate_endpoint(Vc::upcast(self)).await
+    }
+
+    #[turbo_tasks::function]
+    async fn middleware_manifest(self: Vc<Self>) -> Result<Vc<EndpointMiddlewareManifest>> {
+        Ok(match *self.output().await? {
+            PageEndpointOutput::Edge {
+                middleware_manifest,
+                ..
+            } => middleware_manifest,
+            PageEndpointOutput::NodeJs { .. } => EndpointMiddlewareManifest::none(),
+        })
+    }
 }
 
 #[turbo_tasks::value]
@@ -1313,6 +1408,7 @@ enum PageEndpointOutput {
         files: Vc<OutputAssets>,
         server_assets: Vc<OutputAssets>,
         client_assets: Vc<OutputAssets>,
+        middleware_manifest: Vc<EndpointMiddlewareManifest>,
     },
 }
 
@@ -1359,3 +1455,51 @@ pub enum SsrChunk {
         dynamic_import_entries: Vc<DynamicImportedChunks>,
     },
 }
+
+/// Creates the [EdgeFunctionMatcher] of the page at `pathname`. Like app
+/// routes, it matches the pathname prefixed with the `basePath`.
+fn page_edge_function_matcher(base_path: &str, pathname: RcStr) -> EdgeFunctionMatcher {
+    let source = with_base_path(base_path, &pathname);
+    EdgeFunctionMatcher {
+        regex: get_named_middleware_regex(&source).into(),
+        original_source: source.into(),
+        name: pathname,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::page_edge_function_matcher;
+
+    #[test]
+    fn edge_function_matcher_without_base_path() {
+        let matcher = page_edge_function_matcher("", "/blog/[slug]".into());
+
+        assert_eq!(matcher.name, "/blog/[slug]");
+        assert_eq!(matcher.original_source, "/blog/[slug]");
+        assert!(Regex::new(&matcher.regex).unwrap().is_match("/blog/hello"));
+    }
+
+    #[test]
+    fn edge_function_matcher_with_base_path() {
+        let matcher = page_edge_function_matcher("/docs", "/blog/[slug]".into());
+        let regex = Regex::new(&matcher.regex).unwrap();
+
+        assert_eq!(matcher.name, "/blog/[slug]");
+        assert_eq!(matcher.original_source, "/docs/blog/[slug]");
+        assert!(regex.is_match("/docs/blog/hello"));
+        assert!(!regex.is_match("/blog/hello"));
+    }
+
+    #[test]
+    fn edge_function_matcher_of_index_page_with_base_path() {
+        let matcher = page_edge_function_matcher("/docs", "/".into());
+        let regex = Regex::new(&matcher.regex).unwrap();
+
+        assert_eq!(matcher.original_source, "/docs");
+        assert!(regex.is_match("/docs"));
+        assert!(!regex.is_match("/"));
+    }
+}
@@ -1,7 +1,10 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
 use next_core::{all_assets_from_entries, next_manifests::AssetBinding};
 use serde::{Deserialize, Serialize};
-use turbo_tasks::{trace::TraceRawVcs, RcStr, TryFlatJoinIterExt, Vc};
+use turbo_tasks::{
+    trace::TraceRawVcs, RcStr, TryFlatJoinIterExt, TryJoinIterExt, ValueToString, Vc,
+};
 use turbo_tasks_fs::FileSystemPath;
 use turbopack_core::{
     asset::{Asset, AssetContent},
@@ -54,6 +57,62 @@ pub async fn all_server_paths(
     ))
 }
 
+/// The difference between two sets of output assets, keyed by asset path.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default)]
+pub struct OutputAssetsDiff {
+    pub added: Vec<RcStr>,
+    pub removed: Vec<RcStr>,
+    pub changed: Vec<RcStr>,
+}
+
+/// Returns the content hash of all output assets referenced from `assets`,
+/// keyed by their path.
+async fn output_asset_hashes(assets: Vc<OutputAssets>) -> Result<IndexMap<RcStr, u64>> {
+    let all_assets = all_assets_from_entries(assets).await?;
+    Ok(all_assets
+        .iter()
+        .map(|&asset| async move {
+            let path = asset.ident().path().to_string().await?.clone_value();
+            let content_hash = match *asset.content().await? {
+                AssetContent::File(file) => *file.hash().await?,
+                AssetContent::Redirect { .. } => 0,
+            };
+            Ok((path, content_hash))
+        })
+        .try_join()
+        .await?
+        .into_iter()
+        .collect())
+}
+
+/// Compares all output assets referenced from `old` and `new` by path and
+/// content hash.
+#[turbo_tasks::function]
+pub async fn diff_output_assets(
+    old: Vc<OutputAssets>,
+    new: Vc<OutputAssets>,
+) -> Result<Vc<OutputAssetsDiff>> {
+    let old = output_asset_hashes(old).await?;
+    let new = output_asset_hashes(new).await?;
+
+    let mut diff = OutputAssetsDiff::default();
+    for (path, hash) in &new {
+        match old.get(path) {
+            None => diff.added.push(path.clone()),
+            Some(old_hash) if old_hash != hash => diff.changed.push(path.clone()),
+            Some(_) => {}
+        }
+    }
+    diff.removed = old
+        .keys()
+        .filter(|path| !new.contains_key(*path))
+        .cloned()
+        .collect();
+
+    Ok(diff.cell())
+}
+
 /// Return a list of relative paths to `root` for all output assets references
 /// from the `assets` list which are located inside the root path.
 #[turbo_tasks::function]
@@ -122,6 +181,35 @@ pub(crate) async fn get_font_paths_from_root(
     .await
 }
 
+/// Returns the path of `path` relative to `root`. Fails with an error naming
+/// the `kind` of asset, both paths and the route the asset belongs to if `path`
+/// isn't inside `root`.
+pub(crate) fn get_path_in_root<'a>(
+    root: &FileSystemPath,
+    path: &'a FileSystemPath,
+    kind: &str,
+    original_name: &str,
+) -> Result<&'a str> {
+    root.get_path_to(path).with_context(|| {
+        format!("{kind} {path} of route {original_name} is expected to be inside {root}")
+    })
+}
+
+/// Percent-encodes characters of a route name which are not allowed in file
+/// names on every platform, so it can be used as part of an output path.
+pub(crate) fn sanitize_route_path(route: &str) -> RcStr {
+    let mut sanitized = String::with_capacity(route.len());
+    for c in route.chars() {
+        match c {
+            '<' | '>' | ':' | '"' | '|' | '?' | '*' | '\\' => {
+                sanitized.push_str(&format!("%{:02X}", c as u32))
+            }
+            c => sanitized.push(c),
+        }
+    }
+    sanitized.into()
+}
+
 fn get_file_stem(path: &str) -> &str {
     let file_name = if let Some((_, file_name)) = path.rsplit_once('/') {
         file_name
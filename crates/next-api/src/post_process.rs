@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use turbo_tasks::{RcStr, TryJoinIterExt, Vc};
+use turbo_tasks_fs::{json::parse_json_rope_with_source_context, File, FileContent};
+use turbo_tasks_hash::hash_xxh3_hash128;
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    ident::AssetIdent,
+    output::{OutputAsset, OutputAssets},
+};
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionAssetContent(Option<Vc<AssetContent>>);
+
+/// Post-processes output assets before they are written to disk, e.g. to
+/// minify or obfuscate JavaScript chunks.
+#[turbo_tasks::value_trait]
+pub trait OutputAssetPostProcessor {
+    /// Returns the content to write for `asset` instead of its own content,
+    /// or `None` to keep it unchanged.
+    fn process(self: Vc<Self>, asset: Vc<Box<dyn OutputAsset>>) -> Vc<OptionAssetContent>;
+}
+
+/// An [OutputAsset] with the path of another output asset but possibly
+/// different content. Unlike the original asset, it doesn't reference other
+/// assets.
+#[turbo_tasks::value]
+struct PostProcessedOutputAsset {
+    asset: Vc<Box<dyn OutputAsset>>,
+    content: Vc<AssetContent>,
+}
+
+#[turbo_tasks::value_impl]
+impl PostProcessedOutputAsset {
+    #[turbo_tasks::function]
+    fn new(asset: Vc<Box<dyn OutputAsset>>, content: Vc<AssetContent>) -> Vc<Self> {
+        PostProcessedOutputAsset { asset, content }.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl OutputAsset for PostProcessedOutputAsset {
+    #[turbo_tasks::function]
+    fn ident(&self) -> Vc<AssetIdent> {
+        self.asset.ident()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl Asset for PostProcessedOutputAsset {
+    #[turbo_tasks::function]
+    fn content(&self) -> Vc<AssetContent> {
+        self.content
+    }
+}
+
+/// Applies `processors` in order to `asset`. Each processor receives the
+/// result of the previous one.
+#[turbo_tasks::function]
+async fn post_process_output_asset(
+    asset: Vc<Box<dyn OutputAsset>>,
+    processors: Vec<Vc<Box<dyn OutputAssetPostProcessor>>>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let mut asset: Vc<Box<dyn OutputAsset>> =
+        Vc::upcast(PostProcessedOutputAsset::new(asset, asset.content()));
+    for processor in processors {
+        if let Some(content) = *processor.process(asset).await? {
+            asset = Vc::upcast(PostProcessedOutputAsset::new(asset, content));
+        }
+    }
+    Ok(asset)
+}
+
+/// Applies `processors` to all of `assets`, which must already include all
+/// referenced assets, e.g. from `all_assets_from_entries`. The processed
+/// assets don't reference other assets, so that no unprocessed asset is
+/// emitted through a reference. Without processors, `assets` is returned
+/// unchanged.
+#[turbo_tasks::function]
+pub(crate) async fn post_process_output_assets(
+    assets: Vc<OutputAssets>,
+    processors: Vec<Vc<Box<dyn OutputAssetPostProcessor>>>,
+) -> Result<Vc<OutputAssets>> {
+    if processors.is_empty() {
+        return Ok(assets);
+    }
+    Ok(Vc::cell(
+        assets
+            .await?
+            .iter()
+            .map(|&asset| post_process_output_asset(asset, processors.clone()))
+            .collect(),
+    ))
+}
+
+/// The debug ID of a JavaScript chunk, formatted as a UUID. It's derived from
+/// the content of the chunk, so it's stable across builds.
+#[turbo_tasks::function]
+async fn debug_id(chunk: Vc<Box<dyn OutputAsset>>) -> Result<Vc<RcStr>> {
+    let hash = match &*chunk.content().file_content().await? {
+        FileContent::Content(file) => hash_xxh3_hash128(file.content()),
+        FileContent::NotFound => 0,
+    };
+    let hex = format!("{:032x}", hash);
+    Ok(Vc::cell(
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..]
+        )
+        .into(),
+    ))
+}
+
+/// Appends a `//# debugId=` comment to `chunk`.
+#[turbo_tasks::function]
+async fn chunk_with_debug_id(
+    chunk: Vc<Box<dyn OutputAsset>>,
+    debug_id: Vc<RcStr>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let FileContent::Content(file) = &*chunk.content().file_content().await? else {
+        return Ok(chunk);
+    };
+    let code = format!(
+        "{}\n//# debugId={}\n",
+        file.content().to_str()?,
+        debug_id.await?
+    );
+    Ok(Vc::upcast(PostProcessedOutputAsset::new(
+        chunk,
+        AssetContent::file(File::from(code).into()),
+    )))
+}
+
+/// Adds `debug_id` as the `debugId` field of `source_map`.
+#[turbo_tasks::function]
+async fn source_map_with_debug_id(
+    source_map: Vc<Box<dyn OutputAsset>>,
+    debug_id: Vc<RcStr>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let FileContent::Content(file) = &*source_map.content().file_content().await? else {
+        return Ok(source_map);
+    };
+    let mut map: serde_json::Value = parse_json_rope_with_source_context(file.content())?;
+    let Some(fields) = map.as_object_mut() else {
+        return Ok(source_map);
+    };
+    fields.insert("debugId".to_string(), debug_id.await?.as_str().into());
+    Ok(Vc::upcast(PostProcessedOutputAsset::new(
+        source_map,
+        AssetContent::file(File::from(serde_json::to_string(&map)?).into()),
+    )))
+}
+
+/// Adds debug IDs to all JavaScript chunks in `assets` which have a source map
+/// next to them, i.e. `{chunk}.map`, and to these source maps. Like
+/// [post_process_output_assets], this expects `assets` to include all
+/// referenced assets.
+#[turbo_tasks::function]
+pub(crate) async fn inject_debug_ids(assets: Vc<OutputAssets>) -> Result<Vc<OutputAssets>> {
+    let assets = assets
+        .await?
+        .iter()
+        .map(|&asset| async move { Ok((asset.ident().path().await?.path.clone(), asset)) })
+        .try_join()
+        .await?;
+    let paths: HashSet<&str> = assets.iter().map(|(path, _)| path.as_str()).collect();
+    let chunks = assets
+        .iter()
+        .filter(|(path, _)| path.ends_with(".js") && paths.contains(&*format!("{path}.map")))
+        .map(|(path, chunk)| (path.as_str(), *chunk))
+        .collect::<HashMap<_, _>>();
+
+    Ok(Vc::cell(
+        assets
+            .iter()
+            .map(|(path, asset)| {
+                if let Some(&chunk) = chunks.get(path.as_str()) {
+                    chunk_with_debug_id(chunk, debug_id(chunk))
+                } else if let Some(&chunk) = path
+                    .strip_suffix(".map")
+                    .and_then(|chunk_path| chunks.get(chunk_path))
+                {
+                    source_map_with_debug_id(*asset, debug_id(chunk))
+                } else {
+                    *asset
+                }
+            })
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks::{TurboTasks, Vc};
+    use turbo_tasks_fs::{File, FileSystem, VirtualFileSystem};
+    use turbo_tasks_memory::MemoryBackend;
+    use turbopack_core::{
+        asset::AssetContent, output::OutputAsset, virtual_output::VirtualOutputAsset,
+    };
+
+    use super::{post_process_output_assets, OptionAssetContent, OutputAssetPostProcessor};
+    use crate::paths::all_server_paths;
+
+    #[turbo_tasks::value]
+    struct ReplaceContent;
+
+    #[turbo_tasks::value_impl]
+    impl OutputAssetPostProcessor for ReplaceContent {
+        #[turbo_tasks::function]
+        fn process(&self, _asset: Vc<Box<dyn OutputAsset>>) -> Vc<OptionAssetContent> {
+            Vc::cell(Some(AssetContent::file(File::from("processed").into())))
+        }
+    }
+
+    #[tokio::test]
+    async fn server_paths_hash_the_processed_content() {
+        crate::register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        tt.run_once(async {
+            let node_root = VirtualFileSystem::new().root();
+            let chunk = |content: &str| -> Vc<Box<dyn OutputAsset>> {
+                Vc::upcast(VirtualOutputAsset::new(
+                    node_root.join("server/chunk.js".into()),
+                    AssetContent::file(File::from(content).into()),
+                ))
+            };
+            let processed = post_process_output_assets(
+                Vc::cell(vec![chunk("original")]),
+                vec![Vc::upcast(ReplaceContent.cell())],
+            );
+
+            let server_paths = all_server_paths(processed, node_root).await?;
+            let original_paths =
+                all_server_paths(Vc::cell(vec![chunk("original")]), node_root).await?;
+            let expected_paths =
+                all_server_paths(Vc::cell(vec![chunk("processed")]), node_root).await?;
+
+            assert_eq!(server_paths.len(), 1);
+            assert_eq!(server_paths[0].path, "server/chunk.js");
+            assert_eq!(server_paths[0].content_hash, expected_paths[0].content_hash);
+            assert_ne!(server_paths[0].content_hash, original_paths[0].content_hash);
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+}
@@ -1,17 +1,21 @@
-use std::path::MAIN_SEPARATOR;
+use std::{collections::BTreeMap, path::MAIN_SEPARATOR};
 
 use anyhow::{bail, Context, Result};
 use indexmap::{indexmap, map::Entry, IndexMap};
 use next_core::{
     all_assets_from_entries,
     app_structure::find_app_dir,
-    emit_assets, get_edge_chunking_context, get_edge_chunking_context_with_client_assets,
+    content_addressed_path_map, emit_assets, emit_content_addressed_assets,
+    get_edge_chunking_context, get_edge_chunking_context_with_client_assets,
     get_edge_compile_time_info, get_edge_resolve_options_context,
     instrumentation::instrumentation_files,
     middleware::middleware_files,
     mode::NextMode,
-    next_client::{get_client_chunking_context, get_client_compile_time_info},
+    next_client::{
+        get_client_assets_path, get_client_chunking_context, get_client_compile_time_info,
+    },
     next_config::{JsConfig, ModuleIdStrategy as ModuleIdStrategyConfig, NextConfig},
+    next_manifests::MiddlewaresManifestV2,
     next_server::{
         get_server_chunking_context, get_server_chunking_context_with_client_assets,
         get_server_compile_time_info, get_server_module_options_context,
@@ -19,6 +23,7 @@ use next_core::{
     },
     next_telemetry::NextFeatureTelemetry,
     util::NextRuntime,
+    ContentAddressedPathMap,
 };
 use serde::{Deserialize, Serialize};
 use tracing::Instrument;
@@ -27,10 +32,13 @@ use turbo_tasks::{
     graph::{AdjacencyMap, GraphTraversal},
     trace::TraceRawVcs,
     Completion, Completions, IntoTraitRef, RcStr, ReadRef, State, TaskInput, TraitRef,
-    TransientInstance, TryFlatJoinIterExt, Value, Vc,
+    TransientInstance, TryFlatJoinIterExt, TryJoinIterExt, Value, Vc,
 };
 use turbo_tasks_env::{EnvMap, ProcessEnv};
-use turbo_tasks_fs::{DiskFileSystem, FileSystem, FileSystemPath, VirtualFileSystem};
+use turbo_tasks_fs::{
+    DiskFileSystem, File, FileContent, FileSystem, FileSystemPath, VirtualFileSystem,
+};
+use turbo_tasks_hash::encode_hex;
 use turbopack::{
     evaluate_context::node_build_environment, transition::TransitionOptions, ModuleAssetContext,
 };
@@ -38,7 +46,7 @@ use turbopack_core::{
     changed::content_changed,
     chunk::{
         module_id_strategies::{DevModuleIdStrategy, ModuleIdStrategy},
-        ChunkingContext,
+        ChunkSizeThresholds, ChunkingContext,
     },
     compile_time_info::CompileTimeInfo,
     context::AssetContext,
@@ -57,14 +65,16 @@ use turbopack_node::execution_context::ExecutionContext;
 use turbopack_nodejs::NodeJsChunkingContext;
 
 use crate::{
-    app::{AppProject, OptionAppProject, ECMASCRIPT_CLIENT_TRANSITION_NAME},
+    app::{AppProject, AppRouteRuntimes, OptionAppProject, ECMASCRIPT_CLIENT_TRANSITION_NAME},
     build,
     entrypoints::Entrypoints,
     global_module_id_strategy::GlobalModuleIdStrategyBuilder,
+    gzip::COMPRESSED_MANIFESTS,
     instrumentation::InstrumentationEndpoint,
     middleware::MiddlewareEndpoint,
     pages::PagesProject,
-    route::{Endpoint, Route},
+    paths::all_server_paths,
+    route::{EdgeFunctionMatchers, Endpoint, Route, WrittenEndpoint},
     versioned_content_map::{OutputAssetsOperation, VersionedContentMap},
 };
 
@@ -176,6 +186,134 @@ pub struct Instrumentation {
     pub edge: Vc<Box<dyn Endpoint>>,
 }
 
+/// The content of `build-lock.json`. See [Project::write_build_lock].
+#[derive(Serialize, Default)]
+struct BuildLock {
+    /// The content hash of each server file, keyed by its path relative to
+    /// the node root.
+    server: BTreeMap<String, String>,
+    /// The content hash of each client file, keyed by its path relative to
+    /// the client root.
+    client: BTreeMap<String, String>,
+}
+
+impl BuildLock {
+    /// Adds the content hashes of `assets` and all assets they reference. The
+    /// server and client entries are both computed from `assets`, so they hash
+    /// the same content as the emitted files.
+    async fn add_assets(
+        &mut self,
+        assets: Vc<OutputAssets>,
+        node_root: Vc<FileSystemPath>,
+        client_relative_path: Vc<FileSystemPath>,
+    ) -> Result<()> {
+        for (entries, root) in [
+            (&mut self.server, node_root),
+            (&mut self.client, client_relative_path),
+        ] {
+            for path in all_server_paths(assets, root).await?.iter() {
+                entries.insert(path.path.clone(), encode_hex(path.content_hash));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The content of `resource-hints.json`. See [Project::write_resource_hints].
+#[derive(Serialize)]
+struct ResourceHints {
+    /// The origins client assets are loaded from.
+    preconnect: Vec<RcStr>,
+}
+
+/// The content of `server/compressed-manifests.json`. See
+/// [Project::write_compressed_manifests].
+#[derive(Serialize)]
+struct CompressedManifests {
+    gzip: bool,
+    /// The file names of the manifests that are emitted as `.json.gz` files.
+    manifests: &'static [&'static str],
+}
+
+/// Merges `manifest` into `merged`. Middleware and functions that several
+/// manifests define, like the edge function shared by the HTML and RSC
+/// endpoints of an app page, keep their first definition, with the matchers of
+/// all definitions.
+fn merge_middleware_manifests(
+    merged: &mut MiddlewaresManifestV2,
+    manifest: &MiddlewaresManifestV2,
+) {
+    for name in &manifest.sorted_middleware {
+        if !merged.sorted_middleware.contains(name) {
+            merged.sorted_middleware.push(name.clone());
+        }
+    }
+    for (merged_definitions, definitions) in [
+        (&mut merged.middleware, &manifest.middleware),
+        (&mut merged.functions, &manifest.functions),
+    ] {
+        for (name, definition) in definitions {
+            let Some(merged_definition) = merged_definitions.get_mut(name) else {
+                merged_definitions.insert(name.clone(), definition.clone());
+                continue;
+            };
+            for matcher in &definition.matchers {
+                if !merged_definition.matchers.contains(matcher) {
+                    merged_definition.matchers.push(matcher.clone());
+                }
+            }
+        }
+    }
+    if merged.instrumentation.is_none() {
+        merged.instrumentation.clone_from(&manifest.instrumentation);
+    }
+}
+
+/// All endpoints of `entrypoints`.
+fn entrypoint_endpoints(entrypoints: &Entrypoints) -> Vec<Vc<Box<dyn Endpoint>>> {
+    let mut endpoints = vec![
+        entrypoints.pages_document_endpoint,
+        entrypoints.pages_app_endpoint,
+        entrypoints.pages_error_endpoint,
+    ];
+    for route in entrypoints.routes.values() {
+        match route {
+            Route::Page {
+                html_endpoint,
+                data_endpoint,
+            } => endpoints.extend([*html_endpoint, *data_endpoint]),
+            Route::PageApi { endpoint } | Route::AppRoute { endpoint, .. } => {
+                endpoints.push(*endpoint)
+            }
+            Route::AppPage(pages) => endpoints.extend(
+                pages
+                    .iter()
+                    .flat_map(|page| [page.html_endpoint, page.rsc_endpoint]),
+            ),
+            Route::Conflict => {}
+        }
+    }
+    if let Some(middleware) = &entrypoints.middleware {
+        endpoints.push(middleware.endpoint);
+    }
+    if let Some(instrumentation) = &entrypoints.instrumentation {
+        endpoints.extend([instrumentation.node_js, instrumentation.edge]);
+    }
+    endpoints
+}
+
+/// The origin, i.e. scheme, host and port, of an absolute `http(s)` URL.
+fn absolute_url_origin(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let host_len = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    if host_len == 0 {
+        return None;
+    }
+    Some(&url[..url.len() - rest.len() + host_len])
+}
+
 #[turbo_tasks::value]
 pub struct ProjectContainer {
     name: RcStr,
@@ -463,11 +601,11 @@ impl ProjectDefineEnv {
 }
 
 #[turbo_tasks::value(shared)]
-struct ConflictIssue {
-    path: Vc<FileSystemPath>,
-    title: Vc<StyledString>,
-    description: Vc<StyledString>,
-    severity: Vc<IssueSeverity>,
+pub(crate) struct ConflictIssue {
+    pub(crate) path: Vc<FileSystemPath>,
+    pub(crate) title: Vc<StyledString>,
+    pub(crate) description: Vc<StyledString>,
+    pub(crate) severity: Vc<IssueSeverity>,
 }
 
 #[turbo_tasks::value_impl]
@@ -558,20 +696,30 @@ impl Project {
     }
 
     #[turbo_tasks::function]
-    fn project_root_path(self: Vc<Self>) -> Vc<FileSystemPath> {
+    pub fn project_root_path(self: Vc<Self>) -> Vc<FileSystemPath> {
         self.project_fs().root()
     }
 
     #[turbo_tasks::function]
     pub async fn client_relative_path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
-        let next_config = self.next_config().await?;
-        Ok(self.client_root().join(
-            format!(
-                "{}/_next",
-                next_config.base_path.clone().unwrap_or_else(|| "".into()),
-            )
-            .into(),
-        ))
+        let base_path = self.next_config().base_path().await?;
+        Ok(self
+            .client_root()
+            .join(format!("{}/_next", base_path.as_deref().unwrap_or_default()).into()))
+    }
+
+    /// The directory client assets, e.g. images, are emitted to. With
+    /// `flatClientAssets`, this is the client chunks directory, so all client
+    /// files are in a single flat directory. Chunk and asset filenames contain
+    /// a content hash, so they can't collide.
+    #[turbo_tasks::function]
+    pub(super) async fn client_assets_path(self: Vc<Self>) -> Result<Vc<FileSystemPath>> {
+        let client_relative_path = self.client_relative_path();
+        Ok(if *self.next_config().flat_client_assets().await? {
+            client_relative_path.join("static/chunks".into())
+        } else {
+            get_client_assets_path(client_relative_path)
+        })
     }
 
     #[turbo_tasks::function]
@@ -601,6 +749,15 @@ impl Project {
         Ok(self.await?.mode)
     }
 
+    /// Whether manifests are written as compact JSON, see
+    /// [NextConfig::compact_manifests].
+    #[turbo_tasks::function]
+    pub(super) async fn compact_manifests(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(self
+            .next_config()
+            .compact_manifests(self.next_mode().await?.is_development()))
+    }
+
     #[turbo_tasks::function]
     pub(super) async fn js_config(self: Vc<Self>) -> Result<Vc<JsConfig>> {
         Ok(self.await?.js_config)
@@ -639,6 +796,13 @@ impl Project {
         ))
     }
 
+    /// The compile-time defines of client code, including the inlined
+    /// `process.env.NEXT_PUBLIC_*` variables.
+    #[turbo_tasks::function]
+    pub(super) fn client_define_env(&self) -> Vc<EnvMap> {
+        self.define_env.client()
+    }
+
     #[turbo_tasks::function]
     pub(super) async fn server_compile_time_info(self: Vc<Self>) -> Result<Vc<CompileTimeInfo>> {
         let this = self.await?;
@@ -676,10 +840,12 @@ impl Project {
         Ok(get_client_chunking_context(
             self.project_path(),
             self.client_relative_path(),
+            self.client_assets_path(),
             self.next_config().computed_asset_prefix(),
             self.client_compile_time_info().environment(),
             self.next_mode(),
             self.module_id_strategy(),
+            self.next_config().chunk_size_thresholds(),
         ))
     }
 
@@ -687,6 +853,33 @@ impl Project {
     pub(super) fn server_chunking_context(
         self: Vc<Self>,
         client_assets: bool,
+    ) -> Vc<NodeJsChunkingContext> {
+        self.server_chunking_context_with_thresholds(
+            client_assets,
+            self.next_config().chunk_size_thresholds(),
+        )
+    }
+
+    /// A server chunking context that doesn't split chunk groups by size, see
+    /// [ChunkSizeThresholds::unsplit]. A chunk group contains one chunk per
+    /// chunk type, with all of its modules. The runtime and async chunk groups
+    /// are still chunked separately.
+    #[turbo_tasks::function]
+    pub(super) fn unsplit_server_chunking_context(
+        self: Vc<Self>,
+        client_assets: bool,
+    ) -> Vc<NodeJsChunkingContext> {
+        self.server_chunking_context_with_thresholds(
+            client_assets,
+            ChunkSizeThresholds::unsplit().cell(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn server_chunking_context_with_thresholds(
+        self: Vc<Self>,
+        client_assets: bool,
+        chunk_size_thresholds: Vc<ChunkSizeThresholds>,
     ) -> Vc<NodeJsChunkingContext> {
         if client_assets {
             get_server_chunking_context_with_client_assets(
@@ -694,9 +887,11 @@ impl Project {
                 self.project_path(),
                 self.node_root(),
                 self.client_relative_path(),
+                self.client_assets_path(),
                 self.next_config().computed_asset_prefix(),
                 self.server_compile_time_info().environment(),
                 self.module_id_strategy(),
+                chunk_size_thresholds,
             )
         } else {
             get_server_chunking_context(
@@ -705,6 +900,7 @@ impl Project {
                 self.node_root(),
                 self.server_compile_time_info().environment(),
                 self.module_id_strategy(),
+                chunk_size_thresholds,
             )
         }
     }
@@ -720,6 +916,7 @@ impl Project {
                 self.project_path(),
                 self.node_root(),
                 self.client_relative_path(),
+                self.client_assets_path(),
                 self.next_config().computed_asset_prefix(),
                 self.edge_compile_time_info().environment(),
                 self.module_id_strategy(),
@@ -811,6 +1008,205 @@ impl Project {
         Ok(Default::default())
     }
 
+    /// Returns the runtime and type of every app route for the dev overlay.
+    /// See [AppProject::route_runtimes].
+    #[turbo_tasks::function]
+    pub async fn app_route_runtimes(self: Vc<Self>) -> Result<Vc<AppRouteRuntimes>> {
+        Ok(match *self.app_project().await? {
+            Some(app_project) => app_project.route_runtimes(),
+            None => Vc::cell(Default::default()),
+        })
+    }
+
+    /// Returns the names and matchers of all app and pages routes that use the
+    /// edge runtime, sorted by name. Unlike building the endpoints and reading
+    /// their middleware manifests, this only creates the route entries.
+    #[turbo_tasks::function]
+    pub async fn edge_functions(self: Vc<Self>) -> Result<Vc<EdgeFunctionMatchers>> {
+        let mut functions = self.pages_project().edge_functions().await?.clone_value();
+        if let Some(app_project) = &*self.app_project().await? {
+            functions.extend(app_project.edge_functions().await?.iter().cloned());
+        }
+        functions.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Vc::cell(functions))
+    }
+
+    /// Writes the endpoints of all entrypoints, then writes `build-lock.json`
+    /// to the node root. It lists the content hash of every file they wrote,
+    /// so that a later build can be compared against it.
+    #[turbo_tasks::function]
+    pub async fn write_build_lock(self: Vc<Self>) -> Result<Vc<Completion>> {
+        let endpoints = entrypoint_endpoints(&self.entrypoints().await?);
+        let written_endpoints = endpoints
+            .iter()
+            .map(|endpoint| endpoint.write_to_disk())
+            .try_join()
+            .await?;
+
+        let node_root = self.node_root();
+        let client_relative_path = self.client_relative_path();
+        let mut build_lock = BuildLock::default();
+        for (endpoint, written_endpoint) in endpoints.iter().zip(written_endpoints.iter()) {
+            if matches!(**written_endpoint, WrittenEndpoint::Cancelled) {
+                continue;
+            }
+            // Hash the assets the endpoint emitted instead of reading the
+            // written files back from disk.
+            build_lock
+                .add_assets(endpoint.emitted_assets(), node_root, client_relative_path)
+                .await?;
+        }
+
+        node_root
+            .join("build-lock.json".into())
+            .write(
+                FileContent::Content(File::from(serde_json::to_string_pretty(&build_lock)?)).cell(),
+            )
+            .await?;
+        Ok(Completion::new())
+    }
+
+    /// Writes the endpoints of all entrypoints, then writes
+    /// `server/middleware-manifest.json` to the node root. It merges the
+    /// middleware manifests of all edge endpoints, so that deployment tools
+    /// don't have to collect the manifest of each route. Does nothing in
+    /// development, where the dev server writes that file itself.
+    #[turbo_tasks::function]
+    pub async fn write_middleware_manifest(self: Vc<Self>) -> Result<Vc<Completion>> {
+        if *self.next_mode().await? == NextMode::Development {
+            return Ok(Completion::new());
+        }
+
+        let endpoints = entrypoint_endpoints(&self.entrypoints().await?);
+        let written_endpoints = endpoints
+            .iter()
+            .map(|endpoint| endpoint.write_to_disk())
+            .try_join()
+            .await?;
+
+        let mut middleware_manifest = MiddlewaresManifestV2::default();
+        for (endpoint, written_endpoint) in endpoints.iter().zip(written_endpoints.iter()) {
+            if !matches!(**written_endpoint, WrittenEndpoint::Edge { .. }) {
+                continue;
+            }
+            if let Some(manifest) = &endpoint.middleware_manifest().await?.manifest {
+                merge_middleware_manifests(&mut middleware_manifest, manifest);
+            }
+        }
+
+        self.node_root()
+            .join("server/middleware-manifest.json".into())
+            .write(
+                FileContent::Content(File::from(serde_json::to_string_pretty(
+                    &middleware_manifest,
+                )?))
+                .cell(),
+            )
+            .await?;
+        Ok(Completion::new())
+    }
+
+    /// Maps the regular path of every asset the endpoints of all entrypoints
+    /// emit to the path it is stored at with `contentAddressedOutput`, both
+    /// relative to the node root.
+    #[turbo_tasks::function]
+    pub async fn content_addressed_path_map(self: Vc<Self>) -> Result<Vc<ContentAddressedPathMap>> {
+        let node_root = self.node_root();
+        let client_relative_path = self.client_relative_path();
+        let endpoint_path_maps = entrypoint_endpoints(&self.entrypoints().await?)
+            .into_iter()
+            .map(|endpoint| {
+                content_addressed_path_map(
+                    all_assets_from_entries(endpoint.emitted_assets()),
+                    node_root,
+                    client_relative_path,
+                    node_root,
+                )
+            })
+            .try_join()
+            .await?;
+
+        let mut path_map = IndexMap::new();
+        for endpoint_path_map in endpoint_path_maps.iter() {
+            path_map.extend(
+                endpoint_path_map
+                    .iter()
+                    .map(|(path, content_path)| (path.clone(), content_path.clone())),
+            );
+        }
+        path_map.sort_keys();
+        Ok(Vc::cell(path_map))
+    }
+
+    /// Writes the endpoints of all entrypoints, then writes `path-map.json` to
+    /// the node root, which lists [Project::content_addressed_path_map].
+    #[turbo_tasks::function]
+    pub async fn write_content_addressed_path_map(self: Vc<Self>) -> Result<Vc<Completion>> {
+        entrypoint_endpoints(&self.entrypoints().await?)
+            .into_iter()
+            .map(|endpoint| endpoint.write_to_disk())
+            .try_join()
+            .await?;
+
+        let path_map = self.content_addressed_path_map().await?;
+        self.node_root()
+            .join("path-map.json".into())
+            .write(
+                FileContent::Content(File::from(serde_json::to_string_pretty(&*path_map)?)).cell(),
+            )
+            .await?;
+        Ok(Completion::new())
+    }
+
+    /// Writes `server/compressed-manifests.json` to the node root when
+    /// `compressManifests` is enabled, which tells the runtime which manifests
+    /// of each endpoint are gzip-compressed.
+    #[turbo_tasks::function]
+    pub async fn write_compressed_manifests(self: Vc<Self>) -> Result<Vc<Completion>> {
+        if !*self.next_config().compress_manifests().await? {
+            return Ok(Completion::new());
+        }
+
+        let compressed_manifests = CompressedManifests {
+            gzip: true,
+            manifests: COMPRESSED_MANIFESTS,
+        };
+        self.node_root()
+            .join("server/compressed-manifests.json".into())
+            .write(
+                FileContent::Content(File::from(serde_json::to_string_pretty(
+                    &compressed_manifests,
+                )?))
+                .cell(),
+            )
+            .await?;
+        Ok(Completion::new())
+    }
+
+    /// Writes `resource-hints.json` to the node root when client assets are
+    /// served from another origin, i.e. when `assetPrefix` is an absolute URL.
+    /// It lists the origins to preconnect to, so that the HTML runtime can
+    /// inject `<link rel="preconnect">` hints for them.
+    #[turbo_tasks::function]
+    pub async fn write_resource_hints(self: Vc<Self>) -> Result<Vc<Completion>> {
+        let asset_prefix = self.next_config().computed_asset_prefix().await?;
+        let Some(origin) = asset_prefix.as_deref().and_then(absolute_url_origin) else {
+            return Ok(Completion::new());
+        };
+
+        let resource_hints = ResourceHints {
+            preconnect: vec![origin.into()],
+        };
+        self.node_root()
+            .join("resource-hints.json".into())
+            .write(
+                FileContent::Content(File::from(serde_json::to_string_pretty(&resource_hints)?))
+                    .cell(),
+            )
+            .await?;
+        Ok(Completion::new())
+    }
+
     /// Scans the app/pages directories for entry points files (matching the
     /// provided page_extensions).
     #[turbo_tasks::function]
@@ -1148,6 +1544,17 @@ impl Project {
                     .resolve()
                     .await?;
 
+                Ok(Vc::cell(()))
+            } else if *self.next_config().content_addressed_output().await? {
+                let all_output_assets = *all_output_assets.await?;
+                let _ = emit_content_addressed_assets(
+                    all_output_assets,
+                    node_root,
+                    client_relative_path,
+                    node_root,
+                )
+                .resolve()
+                .await?;
                 Ok(Vc::cell(()))
             } else {
                 let _ = emit_assets(
@@ -1323,3 +1730,125 @@ fn all_assets_from_entries_operation(
 ) -> Vc<OutputAssetsOperation> {
     Vc::cell(all_assets_from_entries_operation_inner(operation))
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use next_core::next_manifests::{
+        EdgeFunctionDefinition, MiddlewareMatcher, MiddlewaresManifestV2,
+    };
+    use turbo_tasks::{RcStr, TurboTasks, Vc};
+    use turbo_tasks_fs::{File, FileContent, FileSystem, FileSystemPath, VirtualFileSystem};
+    use turbo_tasks_hash::encode_hex;
+    use turbo_tasks_memory::MemoryBackend;
+    use turbopack_core::{
+        asset::AssetContent, output::OutputAsset, virtual_output::VirtualOutputAsset,
+    };
+
+    use super::{merge_middleware_manifests, BuildLock};
+
+    async fn content_hash(content: &str) -> Result<String> {
+        Ok(encode_hex(
+            *FileContent::from(File::from(content)).cell().hash().await?,
+        ))
+    }
+
+    #[tokio::test]
+    async fn build_lock_hashes_server_and_client_assets() {
+        crate::register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        tt.run_once(async {
+            let root = VirtualFileSystem::new().root();
+            let node_root = root.join(".next".into());
+            let client_relative_path = root.join("_next".into());
+            let asset = |path: Vc<FileSystemPath>, content: &str| {
+                Vc::upcast::<Box<dyn OutputAsset>>(VirtualOutputAsset::new(
+                    path,
+                    AssetContent::file(File::from(content).into()),
+                ))
+            };
+            let assets = Vc::cell(vec![
+                asset(node_root.join("server/app/page.js".into()), "server"),
+                asset(
+                    client_relative_path.join("static/chunks/main.js".into()),
+                    "client",
+                ),
+            ]);
+
+            let mut build_lock = BuildLock::default();
+            build_lock
+                .add_assets(assets, node_root, client_relative_path)
+                .await?;
+
+            assert_eq!(
+                build_lock.server.into_iter().collect::<Vec<_>>(),
+                [(
+                    "server/app/page.js".to_string(),
+                    content_hash("server").await?
+                )]
+            );
+            assert_eq!(
+                build_lock.client.into_iter().collect::<Vec<_>>(),
+                [(
+                    "static/chunks/main.js".to_string(),
+                    content_hash("client").await?
+                )]
+            );
+            Ok(())
+        })
+        .await
+        .unwrap();
+    }
+
+    fn edge_function_manifest(name: &str, matchers: &[&str]) -> MiddlewaresManifestV2 {
+        let name: RcStr = name.into();
+        MiddlewaresManifestV2 {
+            sorted_middleware: vec![name.clone()],
+            functions: [(
+                name.clone(),
+                EdgeFunctionDefinition {
+                    name,
+                    matchers: matchers
+                        .iter()
+                        .map(|&matcher| MiddlewareMatcher {
+                            regexp: Some(matcher.into()),
+                            original_source: matcher.into(),
+                            ..Default::default()
+                        })
+                        .collect(),
+                    ..Default::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn merged_middleware_manifest_combines_matchers() {
+        let mut merged = MiddlewaresManifestV2::default();
+        merge_middleware_manifests(&mut merged, &edge_function_manifest("/blog", &["^/blog$"]));
+        merge_middleware_manifests(
+            &mut merged,
+            &edge_function_manifest("/about", &["^/about$"]),
+        );
+        // The RSC endpoint of a page defines the same function as its HTML endpoint.
+        merge_middleware_manifests(
+            &mut merged,
+            &edge_function_manifest("/blog", &["^/blog$", "^/blog\\.rsc$"]),
+        );
+
+        assert_eq!(merged.sorted_middleware, ["/blog", "/about"]);
+        assert_eq!(merged.functions.len(), 2);
+        assert_eq!(
+            merged.functions["/blog"]
+                .matchers
+                .iter()
+                .map(|matcher| matcher.original_source.as_str())
+                .collect::<Vec<_>>(),
+            ["^/blog$", "^/blog\\.rsc$"]
+        );
+        assert_eq!(merged.functions["/about"].matchers.len(), 1);
+    }
+}
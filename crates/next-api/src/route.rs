@@ -1,8 +1,11 @@
 use anyhow::Result;
 use indexmap::IndexMap;
+use next_core::{next_app::metadata::is_metadata_route, next_manifests::MiddlewaresManifestV2};
 use serde::{Deserialize, Serialize};
-use turbo_tasks::{debug::ValueDebugFormat, trace::TraceRawVcs, Completion, RcStr, Vc};
-use turbopack_core::module::Modules;
+use turbo_tasks::{
+    debug::ValueDebugFormat, trace::TraceRawVcs, Completion, RcStr, State, TaskInput, Vc,
+};
+use turbopack_core::{module::Modules, output::OutputAssets};
 
 use crate::paths::ServerPath;
 
@@ -44,7 +47,58 @@ pub enum Route {
     Conflict,
 }
 
+/// Whether a [Route] renders a page or handles requests itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RouteKind {
+    /// [Route::Page] or [Route::AppPage].
+    Page,
+    /// [Route::PageApi] or [Route::AppRoute].
+    Route,
+}
+
+/// One of the endpoints of an app route. Together with the original name of
+/// the route it identifies an endpoint, see `AppProject::endpoint_for`.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, TaskInput, TraceRawVcs,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AppEndpointKind {
+    /// The HTML endpoint of a page. It is the RSC endpoint when only RSC
+    /// payloads are built.
+    Html,
+    /// The RSC endpoint of a page.
+    Rsc,
+    /// The single endpoint of a route handler or metadata route.
+    Route,
+}
+
 impl Route {
+    /// The kind of the route, or `None` for [Route::Conflict].
+    pub fn kind(&self) -> Option<RouteKind> {
+        match self {
+            Route::Page { .. } | Route::AppPage(_) => Some(RouteKind::Page),
+            Route::PageApi { .. } | Route::AppRoute { .. } => Some(RouteKind::Route),
+            Route::Conflict => None,
+        }
+    }
+
+    /// The original name of an app route, e.g. `/(marketing)/blog/page`. When
+    /// multiple pages match the same pathname, this is the name of the first
+    /// one. Pages router routes and conflicts don't have an original name.
+    pub fn original_name(&self) -> Option<&str> {
+        match self {
+            Route::AppPage(pages) => pages.first().map(|page| page.original_name.as_str()),
+            Route::AppRoute { original_name, .. } => Some(original_name),
+            Route::Page { .. } | Route::PageApi { .. } | Route::Conflict => None,
+        }
+    }
+
+    /// Whether the route is an app route serving metadata, e.g. `robots.txt`
+    /// or an Open Graph image.
+    pub fn is_metadata(&self) -> bool {
+        matches!(self, Route::AppRoute { original_name, .. } if is_metadata_route(original_name))
+    }
+
     pub async fn resolve(&mut self) -> Result<()> {
         match self {
             Route::Page {
@@ -77,6 +131,87 @@ pub trait Endpoint {
     fn server_changed(self: Vc<Self>) -> Vc<Completion>;
     fn client_changed(self: Vc<Self>) -> Vc<Completion>;
     fn root_modules(self: Vc<Self>) -> Vc<Modules>;
+    /// The output assets [Endpoint::write_to_disk] writes, not including the
+    /// assets they reference.
+    fn emitted_assets(self: Vc<Self>) -> Vc<OutputAssets>;
+    /// Forces the next [Endpoint::write_to_disk] to recompute the endpoint's
+    /// output instead of returning the cached result, e.g. because a plugin
+    /// detected a change the file watcher can't see.
+    ///
+    /// Only the task building the endpoint's output is re-executed. The tasks
+    /// it depends on, e.g. chunking or reading files, still return their
+    /// cached results unless they were invalidated themselves, so invalidating
+    /// is cheap but doesn't re-read files from disk. Invalidate the filesystem
+    /// for that, which rebuilds everything reading from it.
+    ///
+    /// Calls are cached like those of any task function until the endpoint's
+    /// output has been recomputed, so calling this several times before the
+    /// next `write_to_disk` recomputes the output only once. Call it from a
+    /// one-off task, as a task that keeps depending on it would re-execute it
+    /// every time the output is recomputed.
+    fn invalidate(self: Vc<Self>) -> Vc<Completion>;
+    /// The middleware manifest [Endpoint::write_to_disk] writes for an edge
+    /// endpoint, without reading it back from disk.
+    fn middleware_manifest(self: Vc<Self>) -> Vc<EndpointMiddlewareManifest>;
+}
+
+/// A counter that is bumped to invalidate the output of an endpoint, see
+/// [Endpoint::invalidate].
+#[turbo_tasks::value]
+pub(crate) struct EndpointRevision {
+    revision: State<u64>,
+    /// The revision the output was last built for. [Endpoint::invalidate]
+    /// depends on it, so its cached result is discarded once the output has
+    /// been rebuilt and the next call invalidates the output again.
+    built: State<u64>,
+}
+
+impl EndpointRevision {
+    /// Makes the calling task, which builds the endpoint's output, depend on
+    /// the revision, so it's re-executed when the endpoint is invalidated.
+    /// Returns the revision.
+    pub(crate) fn track(&self) -> u64 {
+        let revision = *self.revision.get();
+        self.built.update_conditionally(|built| {
+            let changed = *built != revision;
+            *built = revision;
+            changed
+        });
+        revision
+    }
+
+    /// The current revision, without depending on it.
+    pub(crate) fn current(&self) -> u64 {
+        *self.revision.get_untracked()
+    }
+
+    /// Invalidates the output of the endpoint.
+    pub(crate) fn bump(&self) {
+        self.revision.update_conditionally(|revision| {
+            *revision += 1;
+            true
+        });
+    }
+}
+
+/// The revision of an endpoint. This task has no dependencies, so it's never
+/// re-executed and its state lives as long as the endpoint's task.
+#[turbo_tasks::function]
+pub(crate) fn endpoint_revision(_endpoint: Vc<Box<dyn Endpoint>>) -> Vc<EndpointRevision> {
+    EndpointRevision {
+        revision: State::new(0),
+        built: State::new(0),
+    }
+    .cell()
+}
+
+/// Implements [Endpoint::invalidate] for endpoints whose output depends on
+/// [EndpointRevision::track].
+pub(crate) async fn invalidate_endpoint(endpoint: Vc<Box<dyn Endpoint>>) -> Result<Vc<Completion>> {
+    let endpoint_revision = endpoint_revision(endpoint).await?;
+    endpoint_revision.built.get();
+    endpoint_revision.bump();
+    Ok(Completion::new())
 }
 
 #[turbo_tasks::value(shared)]
@@ -92,9 +227,189 @@ pub enum WrittenEndpoint {
         server_paths: Vec<ServerPath>,
         client_paths: Vec<RcStr>,
     },
+    /// The build was cancelled before it wrote anything, see
+    /// `AppProject::cancel_pending_builds`.
+    Cancelled,
 }
 
 /// The routes as map from pathname to route. (pathname includes the leading
 /// slash)
 #[turbo_tasks::value(transparent)]
 pub struct Routes(IndexMap<RcStr, Route>);
+
+#[turbo_tasks::value_impl]
+impl Routes {
+    /// The routes rendering a page, i.e. [Route::Page] and [Route::AppPage].
+    #[turbo_tasks::function]
+    pub async fn pages(self: Vc<Self>) -> Result<Vc<Self>> {
+        filter_routes(self, |route| route.kind() == Some(RouteKind::Page)).await
+    }
+
+    /// The routes handling requests themselves, i.e. [Route::PageApi] and
+    /// [Route::AppRoute], except for metadata routes.
+    #[turbo_tasks::function]
+    pub async fn route_handlers(self: Vc<Self>) -> Result<Vc<Self>> {
+        filter_routes(self, |route| {
+            route.kind() == Some(RouteKind::Route) && !route.is_metadata()
+        })
+        .await
+    }
+
+    /// The metadata routes. See [Route::is_metadata].
+    #[turbo_tasks::function]
+    pub async fn metadata(self: Vc<Self>) -> Result<Vc<Self>> {
+        filter_routes(self, Route::is_metadata).await
+    }
+}
+
+/// The routes of `routes` matching `predicate`, in their original order.
+async fn filter_routes(
+    routes: Vc<Routes>,
+    predicate: impl Fn(&Route) -> bool,
+) -> Result<Vc<Routes>> {
+    Ok(Vc::cell(
+        routes
+            .await?
+            .iter()
+            .filter(|(_, route)| predicate(route))
+            .map(|(pathname, route)| (pathname.clone(), route.clone()))
+            .collect(),
+    ))
+}
+
+/// The name and matcher of an edge function, as they appear in its entry in
+/// the middleware manifest.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone)]
+pub struct EdgeFunctionMatcher {
+    pub name: RcStr,
+    pub original_source: RcStr,
+    pub regex: RcStr,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct EdgeFunctionMatchers(Vec<EdgeFunctionMatcher>);
+
+/// The middleware manifest of an endpoint, which is `None` unless it uses the
+/// edge runtime. See [Endpoint::middleware_manifest].
+#[turbo_tasks::value(serialization = "none", cell = "new", eq = "manual")]
+pub struct EndpointMiddlewareManifest {
+    #[turbo_tasks(debug_ignore, trace_ignore)]
+    pub manifest: Option<MiddlewaresManifestV2>,
+}
+
+#[turbo_tasks::value_impl]
+impl EndpointMiddlewareManifest {
+    #[turbo_tasks::function]
+    pub fn none() -> Vc<Self> {
+        EndpointMiddlewareManifest { manifest: None }.cell()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use anyhow::Result;
+    use turbo_tasks::{Completion, TurboTasks, Vc};
+    use turbo_tasks_memory::MemoryBackend;
+    use turbopack_core::{module::Modules, output::OutputAssets};
+
+    use super::{
+        endpoint_revision, invalidate_endpoint, Endpoint, EndpointMiddlewareManifest,
+        WrittenEndpoint,
+    };
+
+    /// How often [CountingEndpoint] built its output.
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+    #[turbo_tasks::value]
+    struct CountingEndpoint;
+
+    #[turbo_tasks::function]
+    fn counting_endpoint() -> Vc<Box<dyn Endpoint>> {
+        Vc::upcast(CountingEndpoint.cell())
+    }
+
+    #[turbo_tasks::value_impl]
+    impl Endpoint for CountingEndpoint {
+        #[turbo_tasks::function]
+        async fn write_to_disk(self: Vc<Self>) -> Result<Vc<WrittenEndpoint>> {
+            endpoint_revision(Vc::upcast(self)).await?.track();
+            BUILDS.fetch_add(1, Ordering::SeqCst);
+            Ok(WrittenEndpoint::Edge {
+                server_paths: vec![],
+                client_paths: vec![],
+            }
+            .cell())
+        }
+
+        #[turbo_tasks::function]
+        fn server_changed(self: Vc<Self>) -> Vc<Completion> {
+            Completion::new()
+        }
+
+        #[turbo_tasks::function]
+        fn client_changed(self: Vc<Self>) -> Vc<Completion> {
+            Completion::new()
+        }
+
+        #[turbo_tasks::function]
+        fn root_modules(self: Vc<Self>) -> Vc<Modules> {
+            Vc::cell(vec![])
+        }
+
+        #[turbo_tasks::function]
+        fn emitted_assets(self: Vc<Self>) -> Vc<OutputAssets> {
+            Vc::cell(vec![])
+        }
+
+        #[turbo_tasks::function]
+        async fn invalidate(self: Vc<Self>) -> Result<Vc<Completion>> {
+            invalidate_endpoint(Vc::upcast(self)).await
+        }
+
+        #[turbo_tasks::function]
+        fn middleware_manifest(self: Vc<Self>) -> Vc<EndpointMiddlewareManifest> {
+            EndpointMiddlewareManifest::none()
+        }
+    }
+
+    #[tokio::test]
+    async fn write_to_disk_recomputes_after_invalidate() {
+        crate::register();
+        let tt = TurboTasks::new(MemoryBackend::default());
+        let write_to_disk = || {
+            tt.run_once(async {
+                counting_endpoint()
+                    .write_to_disk()
+                    .strongly_consistent()
+                    .await?;
+                Ok(BUILDS.load(Ordering::SeqCst))
+            })
+        };
+        let invalidate = || {
+            tt.run_once(async {
+                counting_endpoint()
+                    .invalidate()
+                    .strongly_consistent()
+                    .await?;
+                Ok(())
+            })
+        };
+
+        assert_eq!(write_to_disk().await.unwrap(), 1);
+        assert_eq!(write_to_disk().await.unwrap(), 1);
+
+        invalidate().await.unwrap();
+        assert_eq!(write_to_disk().await.unwrap(), 2);
+
+        // Invalidating again before the output was rebuilt is a no-op.
+        invalidate().await.unwrap();
+        invalidate().await.unwrap();
+        assert_eq!(write_to_disk().await.unwrap(), 3);
+
+        invalidate().await.unwrap();
+        assert_eq!(write_to_disk().await.unwrap(), 4);
+    }
+}
@@ -42,6 +42,8 @@ pub(crate) async fn create_server_actions_manifest(
     project_path: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     page_name: &str,
+    manifest_path_prefix: &str,
+    manifest_name: &str,
     runtime: NextRuntime,
     asset_context: Vc<Box<dyn AssetContext>>,
     chunking_context: Vc<Box<dyn ChunkingContext>>,
@@ -57,10 +59,37 @@ pub(crate) async fn create_server_actions_manifest(
         .as_chunk_item(Vc::upcast(chunking_context))
         .id()
         .to_string();
-    let manifest = build_manifest(node_root, page_name, runtime, actions, loader_id).await?;
+    let manifest = build_manifest(
+        node_root,
+        page_name,
+        manifest_path_prefix,
+        manifest_name,
+        runtime,
+        actions,
+        loader_id,
+    )
+    .await?;
     Ok((evaluable, manifest))
 }
 
+/// The hashed ids of the server actions reachable from the RSC entry point,
+/// i.e. the actions listed in the manifest created by
+/// [create_server_actions_manifest].
+#[turbo_tasks::function]
+pub(crate) async fn server_action_ids(
+    rsc_entry: Vc<Box<dyn Module>>,
+    server_reference_modules: Vc<Vec<Vc<Box<dyn Module>>>>,
+    asset_context: Vc<Box<dyn AssetContext>>,
+) -> Result<Vc<Vec<RcStr>>> {
+    let actions = get_actions(rsc_entry, server_reference_modules, asset_context).await?;
+    Ok(Vc::cell(
+        actions
+            .keys()
+            .map(|hash_id| hash_id.as_str().into())
+            .collect(),
+    ))
+}
+
 /// Builds the "action loader" entry point, which reexports every found action
 /// behind a lazy dynamic import.
 ///
@@ -121,13 +150,14 @@ async fn build_server_actions_loader(
 async fn build_manifest(
     node_root: Vc<FileSystemPath>,
     page_name: &str,
+    manifest_path_prefix: &str,
+    manifest_name: &str,
     runtime: NextRuntime,
     actions: Vc<AllActions>,
     loader_id: Vc<RcStr>,
 ) -> Result<Vc<Box<dyn OutputAsset>>> {
-    let manifest_path_prefix = page_name;
-    let manifest_path = node_root
-        .join(format!("server/app{manifest_path_prefix}/server-reference-manifest.json",).into());
+    let manifest_path =
+        node_root.join(format!("server/app{manifest_path_prefix}/{manifest_name}.json").into());
     let mut manifest = ServerReferenceManifest {
         ..Default::default()
     };
@@ -0,0 +1,109 @@
+use anyhow::Result;
+use next_core::{
+    all_assets_from_entries,
+    next_manifests::{ChunkStats, ModuleStats, RouteStats},
+};
+use turbo_tasks::{TryFlatJoinIterExt, TryJoinIterExt, ValueToString, Vc};
+use turbo_tasks_fs::{File, FileContent, FileSystemPath};
+use turbopack_browser::ecmascript::EcmascriptDevChunk;
+use turbopack_core::{
+    asset::{Asset, AssetContent},
+    chunk::ChunkItem,
+    output::{OutputAsset, OutputAssets},
+    virtual_output::VirtualOutputAsset,
+};
+use turbopack_ecmascript::chunk::{EcmascriptChunk, EcmascriptChunkItem};
+use turbopack_nodejs::EcmascriptBuildNodeChunk;
+
+/// The number of modules listed per chunk.
+const TOP_MODULES: usize = 10;
+
+/// Creates a `stats.json` at `path`, describing the sizes of the client and
+/// server chunks of a route and the modules contributing most to them.
+#[turbo_tasks::function]
+pub async fn create_route_stats(
+    path: Vc<FileSystemPath>,
+    client_root: Vc<FileSystemPath>,
+    client_assets: Vc<OutputAssets>,
+    node_root: Vc<FileSystemPath>,
+    server_assets: Vc<OutputAssets>,
+) -> Result<Vc<Box<dyn OutputAsset>>> {
+    let stats = RouteStats {
+        client_chunks: chunk_stats(client_root, client_assets).await?,
+        server_chunks: chunk_stats(node_root, server_assets).await?,
+    };
+    Ok(Vc::upcast(VirtualOutputAsset::new(
+        path,
+        AssetContent::file(File::from(serde_json::to_string_pretty(&stats)?).into()),
+    )))
+}
+
+async fn chunk_stats(
+    root: Vc<FileSystemPath>,
+    assets: Vc<OutputAssets>,
+) -> Result<Vec<ChunkStats>> {
+    let root = &*root.await?;
+    let all_assets = all_assets_from_entries(assets).await?;
+    all_assets
+        .iter()
+        .map(|&asset| async move {
+            let path = asset.ident().path().await?;
+            let Some(path) = root.get_path_to(&path) else {
+                return Ok(None);
+            };
+            if !path.ends_with(".js") && !path.ends_with(".css") {
+                return Ok(None);
+            }
+
+            let size = match &*asset.content().file_content().await? {
+                FileContent::Content(file) => file.content().len(),
+                FileContent::NotFound => 0,
+            };
+            let modules = match ecmascript_chunk(asset).await? {
+                Some(chunk) => largest_modules(chunk).await?,
+                None => vec![],
+            };
+            Ok(Some(ChunkStats {
+                path: path.into(),
+                size,
+                modules,
+            }))
+        })
+        .try_flat_join()
+        .await
+}
+
+/// Returns the chunk an ecmascript chunk output asset is generated from.
+async fn ecmascript_chunk(asset: Vc<Box<dyn OutputAsset>>) -> Result<Option<Vc<EcmascriptChunk>>> {
+    if let Some(chunk) = Vc::try_resolve_downcast_type::<EcmascriptDevChunk>(asset).await? {
+        return Ok(Some(chunk.chunk()));
+    }
+    if let Some(chunk) = Vc::try_resolve_downcast_type::<EcmascriptBuildNodeChunk>(asset).await? {
+        return Ok(Some(chunk.chunk()));
+    }
+    Ok(None)
+}
+
+async fn largest_modules(chunk: Vc<EcmascriptChunk>) -> Result<Vec<ModuleStats>> {
+    let mut modules = chunk
+        .await?
+        .content
+        .await?
+        .chunk_items
+        .iter()
+        .map(|&(chunk_item, async_info)| async move {
+            let size = chunk_item
+                .content_with_async_module_info(async_info)
+                .await
+                .map_or(0, |content| content.inner_code.len());
+            Ok(ModuleStats {
+                name: chunk_item.asset_ident().to_string().await?.clone_value(),
+                size,
+            })
+        })
+        .try_join()
+        .await?;
+    modules.sort_by(|a, b| b.size.cmp(&a.size));
+    modules.truncate(TOP_MODULES);
+    Ok(modules)
+}
@@ -69,6 +69,9 @@ pub struct NextSegmentConfig {
     pub runtime: Option<NextRuntime>,
     pub preferred_region: Option<Vec<RcStr>>,
     pub experimental_ppr: Option<bool>,
+    /// Whether a route handler streams its responses, which the runtime then
+    /// doesn't buffer. Set by `export const streaming = true`.
+    pub streaming: Option<bool>,
     /// Whether these metadata exports are defined in the source file.
     pub generate_image_metadata: bool,
     pub generate_sitemaps: bool,
@@ -105,6 +108,43 @@ impl NextSegmentConfig {
         *experimental_ppr = experimental_ppr.or(parent.experimental_ppr);
     }
 
+    /// Suggests a `Cache-Control` header for the responses of a route with this
+    /// config. Dynamic routes aren't cached, static routes are cached
+    /// indefinitely and routes with a `revalidate` interval are cached for that
+    /// long. Returns `None` when the config doesn't decide whether the route is
+    /// static.
+    pub fn cache_control_hint(&self) -> Option<RcStr> {
+        if self.dynamic == Some(NextSegmentDynamic::ForceDynamic) {
+            return Some("no-store".into());
+        }
+        match self.revalidate {
+            Some(NextRevalidate::Frequency { seconds: 0 }) => Some("no-store".into()),
+            Some(NextRevalidate::Frequency { seconds }) => {
+                Some(format!("s-maxage={seconds}, stale-while-revalidate").into())
+            }
+            Some(NextRevalidate::Never | NextRevalidate::ForceCache) => {
+                Some("public, max-age=31536000, immutable".into())
+            }
+            None => match self.dynamic {
+                Some(NextSegmentDynamic::ForceStatic | NextSegmentDynamic::Error) => {
+                    Some("public, max-age=31536000, immutable".into())
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether a route with this config can be prerendered at build time,
+    /// i.e. doesn't opt into dynamic rendering through `dynamic =
+    /// "force-dynamic"`, `revalidate = 0` or `fetchCache = "force-no-store"`.
+    /// Whether the route uses dynamic functions like `cookies()` is only
+    /// known when rendering it, so this isn't taken into account.
+    pub fn allows_prerender(&self) -> bool {
+        self.dynamic != Some(NextSegmentDynamic::ForceDynamic)
+            && self.revalidate != Some(NextRevalidate::Frequency { seconds: 0 })
+            && self.fetch_cache != Some(NextSegmentFetchCache::ForceNoStore)
+    }
+
     /// Applies a config from a parallel route to this config, returning an
     /// error if there are conflicting values.
     pub fn apply_parallel_config(&mut self, parallel_config: &Self) -> Result<()> {
@@ -462,6 +502,15 @@ fn parse_config_value(
 
             config.experimental_ppr = Some(val);
         }
+        "streaming" => {
+            let value = eval_context.eval(init);
+            let Some(val) = value.as_bool() else {
+                invalid_config("`streaming` needs to be a static boolean", &value);
+                return;
+            };
+
+            config.streaming = Some(val);
+        }
         _ => {}
     }
 }
@@ -506,3 +555,141 @@ pub async fn parse_segment_config_from_loader_tree_internal(
 
     Ok(config)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{NextRevalidate, NextSegmentConfig, NextSegmentDynamic, NextSegmentFetchCache};
+
+    fn hint(
+        dynamic: Option<NextSegmentDynamic>,
+        revalidate: Option<NextRevalidate>,
+    ) -> Option<String> {
+        NextSegmentConfig {
+            dynamic,
+            revalidate,
+            ..Default::default()
+        }
+        .cache_control_hint()
+        .map(|hint| hint.to_string())
+    }
+
+    #[test]
+    fn test_cache_control_hint_dynamic() {
+        assert_eq!(
+            hint(Some(NextSegmentDynamic::ForceDynamic), None).as_deref(),
+            Some("no-store")
+        );
+        assert_eq!(
+            hint(
+                Some(NextSegmentDynamic::ForceDynamic),
+                Some(NextRevalidate::Never)
+            )
+            .as_deref(),
+            Some("no-store")
+        );
+        assert_eq!(
+            hint(None, Some(NextRevalidate::Frequency { seconds: 0 })).as_deref(),
+            Some("no-store")
+        );
+    }
+
+    #[test]
+    fn test_cache_control_hint_static() {
+        let immutable = Some("public, max-age=31536000, immutable");
+        assert_eq!(
+            hint(Some(NextSegmentDynamic::ForceStatic), None).as_deref(),
+            immutable
+        );
+        assert_eq!(
+            hint(Some(NextSegmentDynamic::Error), None).as_deref(),
+            immutable
+        );
+        assert_eq!(
+            hint(None, Some(NextRevalidate::Never)).as_deref(),
+            immutable
+        );
+        assert_eq!(
+            hint(None, Some(NextRevalidate::ForceCache)).as_deref(),
+            immutable
+        );
+    }
+
+    #[test]
+    fn test_cache_control_hint_revalidate() {
+        assert_eq!(
+            hint(
+                Some(NextSegmentDynamic::ForceStatic),
+                Some(NextRevalidate::Frequency { seconds: 60 })
+            )
+            .as_deref(),
+            Some("s-maxage=60, stale-while-revalidate")
+        );
+    }
+
+    #[test]
+    fn test_allows_prerender() {
+        assert!(NextSegmentConfig::default().allows_prerender());
+        assert!(NextSegmentConfig {
+            dynamic: Some(NextSegmentDynamic::ForceStatic),
+            revalidate: Some(NextRevalidate::Frequency { seconds: 60 }),
+            ..Default::default()
+        }
+        .allows_prerender());
+        assert!(!NextSegmentConfig {
+            dynamic: Some(NextSegmentDynamic::ForceDynamic),
+            ..Default::default()
+        }
+        .allows_prerender());
+        assert!(!NextSegmentConfig {
+            revalidate: Some(NextRevalidate::Frequency { seconds: 0 }),
+            ..Default::default()
+        }
+        .allows_prerender());
+        assert!(!NextSegmentConfig {
+            fetch_cache: Some(NextSegmentFetchCache::ForceNoStore),
+            ..Default::default()
+        }
+        .allows_prerender());
+    }
+
+    #[test]
+    fn test_fetch_cache_inherited_from_parent() {
+        let parent = NextSegmentConfig {
+            fetch_cache: Some(NextSegmentFetchCache::ForceNoStore),
+            ..Default::default()
+        };
+        let mut config = NextSegmentConfig::default();
+        config.apply_parent_config(&parent);
+        assert_eq!(
+            config.fetch_cache,
+            Some(NextSegmentFetchCache::ForceNoStore)
+        );
+    }
+
+    #[test]
+    fn test_fetch_cache_overrides_parent() {
+        let parent = NextSegmentConfig {
+            fetch_cache: Some(NextSegmentFetchCache::ForceNoStore),
+            ..Default::default()
+        };
+        let mut config = NextSegmentConfig {
+            fetch_cache: Some(NextSegmentFetchCache::OnlyCache),
+            ..Default::default()
+        };
+        config.apply_parent_config(&parent);
+        assert_eq!(config.fetch_cache, Some(NextSegmentFetchCache::OnlyCache));
+    }
+
+    #[test]
+    fn test_fetch_cache_unset() {
+        let mut config = NextSegmentConfig::default();
+        config.apply_parent_config(&NextSegmentConfig::default());
+        assert_eq!(config.fetch_cache, None);
+    }
+
+    #[test]
+    fn test_cache_control_hint_auto() {
+        assert_eq!(hint(None, None), None);
+        assert_eq!(hint(Some(NextSegmentDynamic::Auto), None), None);
+    }
+}
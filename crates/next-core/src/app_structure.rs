@@ -26,6 +26,7 @@ use crate::{
         },
         AppPage, AppPath, PageSegment, PageType,
     },
+    next_config::MetadataConflictPolicy,
     next_import_map::get_next_package,
 };
 
@@ -422,6 +423,50 @@ impl LoaderTree {
         false
     }
 
+    /// Returns the root layouts of this tree, i.e. the outermost layout on
+    /// every branch.
+    pub fn root_layouts(&self) -> Vec<Vc<FileSystemPath>> {
+        if let Some(layout) = self.components.layout {
+            return vec![layout];
+        }
+
+        let mut root_layouts = Vec::new();
+        for (_, tree) in &self.parallel_routes {
+            for layout in tree.root_layouts() {
+                if !root_layouts.contains(&layout) {
+                    root_layouts.push(layout);
+                }
+            }
+        }
+
+        root_layouts
+    }
+
+    /// Returns the error boundaries of this tree, i.e. its `error`,
+    /// `global-error` and `not-found` files.
+    pub fn error_boundaries(&self) -> Vec<Vc<FileSystemPath>> {
+        let mut error_boundaries = Vec::new();
+        self.collect_error_boundaries(&mut error_boundaries);
+        error_boundaries
+    }
+
+    fn collect_error_boundaries(&self, error_boundaries: &mut Vec<Vc<FileSystemPath>>) {
+        let Components {
+            error,
+            global_error,
+            not_found,
+            ..
+        } = &self.components;
+        for &path in [error, global_error, not_found].into_iter().flatten() {
+            if !error_boundaries.contains(&path) {
+                error_boundaries.push(path);
+            }
+        }
+        for tree in self.parallel_routes.values() {
+            tree.collect_error_boundaries(error_boundaries);
+        }
+    }
+
     /// Returns whether or not the only match in this tree is for a catch-all
     /// route.
     pub fn has_only_catchall(&self) -> bool {
@@ -646,8 +691,9 @@ fn add_app_metadata_route(
     result: &mut IndexMap<AppPath, Entrypoint>,
     page: AppPage,
     metadata: MetadataItem,
+    conflict_policy: MetadataConflictPolicy,
 ) {
-    let e = match result.entry(page.clone().into()) {
+    let mut e = match result.entry(page.clone().into()) {
         Entry::Occupied(e) => e,
         Entry::Vacant(e) => {
             e.insert(Entrypoint::AppMetadata { page, metadata });
@@ -671,11 +717,90 @@ fn add_app_metadata_route(
             conflict("route", existing_page);
         }
         Entrypoint::AppMetadata {
-            page: existing_page,
+            metadata: existing_metadata,
             ..
         } => {
-            conflict("metadata", existing_page);
+            MetadataConflictIssue {
+                app_dir,
+                page: page.clone(),
+                first: existing_metadata.into_path(),
+                second: metadata.into_path(),
+                policy: conflict_policy,
+            }
+            .cell()
+            .emit();
+            if conflict_policy == MetadataConflictPolicy::LastWins {
+                e.insert(Entrypoint::AppMetadata { page, metadata });
+            }
+        }
+    }
+}
+
+/// Two metadata files resolve to the same route. See [MetadataConflictPolicy].
+#[turbo_tasks::value]
+struct MetadataConflictIssue {
+    app_dir: Vc<FileSystemPath>,
+    page: AppPage,
+    first: Vc<FileSystemPath>,
+    second: Vc<FileSystemPath>,
+    policy: MetadataConflictPolicy,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for MetadataConflictIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> Vc<IssueSeverity> {
+        match self.policy {
+            MetadataConflictPolicy::Error => IssueSeverity::Error,
+            MetadataConflictPolicy::FirstWins | MetadataConflictPolicy::LastWins => {
+                IssueSeverity::Warning
+            }
         }
+        .cell()
+    }
+
+    #[turbo_tasks::function]
+    fn stage(&self) -> Vc<IssueStage> {
+        IssueStage::AppStructure.cell()
+    }
+
+    #[turbo_tasks::function]
+    fn file_path(&self) -> Vc<FileSystemPath> {
+        self.second
+    }
+
+    #[turbo_tasks::function]
+    async fn title(&self) -> Result<Vc<StyledString>> {
+        Ok(
+            StyledString::Text(format!("Conflicting metadata files for {}", self.page).into())
+                .cell(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<Vc<OptionStyledString>> {
+        let app_dir = self.app_dir.await?;
+        let first = self.first.await?;
+        let second = self.second.await?;
+        let used = match self.policy {
+            MetadataConflictPolicy::Error | MetadataConflictPolicy::FirstWins => &first,
+            MetadataConflictPolicy::LastWins => &second,
+        };
+        let relative =
+            |path: &FileSystemPath| app_dir.get_path_to(path).unwrap_or(&path.path).to_string();
+        Ok(Vc::cell(Some(
+            StyledString::Text(
+                format!(
+                    "Both {} and {} resolve to {}. Only {} is used.",
+                    relative(&first),
+                    relative(&second),
+                    self.page,
+                    relative(used),
+                )
+                .into(),
+            )
+            .cell(),
+        )))
     }
 }
 
@@ -683,12 +808,14 @@ fn add_app_metadata_route(
 pub fn get_entrypoints(
     app_dir: Vc<FileSystemPath>,
     page_extensions: Vc<Vec<RcStr>>,
+    metadata_conflict_policy: Vc<MetadataConflictPolicy>,
 ) -> Vc<Entrypoints> {
     directory_tree_to_entrypoints(
         app_dir,
         get_directory_tree(app_dir, page_extensions),
         get_global_metadata(app_dir, page_extensions),
         Default::default(),
+        metadata_conflict_policy,
     )
 }
 
@@ -698,6 +825,7 @@ fn directory_tree_to_entrypoints(
     directory_tree: Vc<DirectoryTree>,
     global_metadata: Vc<GlobalMetadata>,
     root_layouts: Vc<Vec<Vc<FileSystemPath>>>,
+    metadata_conflict_policy: Vc<MetadataConflictPolicy>,
 ) -> Vc<Entrypoints> {
     directory_tree_to_entrypoints_internal(
         app_dir,
@@ -706,6 +834,7 @@ fn directory_tree_to_entrypoints(
         directory_tree,
         AppPage::new(),
         root_layouts,
+        metadata_conflict_policy,
     )
 }
 
@@ -1005,6 +1134,7 @@ async fn directory_tree_to_entrypoints_internal(
     directory_tree: Vc<DirectoryTree>,
     app_page: AppPage,
     root_layouts: Vc<Vec<Vc<FileSystemPath>>>,
+    metadata_conflict_policy: Vc<MetadataConflictPolicy>,
 ) -> Result<Vc<Entrypoints>> {
     let span = tracing::info_span!("build layout trees", name = display(&app_page));
     directory_tree_to_entrypoints_internal_untraced(
@@ -1014,6 +1144,7 @@ async fn directory_tree_to_entrypoints_internal(
         directory_tree,
         app_page,
         root_layouts,
+        metadata_conflict_policy,
     )
     .instrument(span)
     .await
@@ -1026,8 +1157,10 @@ async fn directory_tree_to_entrypoints_internal_untraced(
     directory_tree: Vc<DirectoryTree>,
     app_page: AppPage,
     root_layouts: Vc<Vec<Vc<FileSystemPath>>>,
+    metadata_conflict_policy: Vc<MetadataConflictPolicy>,
 ) -> Result<Vc<Entrypoints>> {
     let mut result = IndexMap::new();
+    let metadata_conflict_policy_value = *metadata_conflict_policy.await?;
 
     let directory_tree_vc = directory_tree;
     let directory_tree = &*directory_tree.await?;
@@ -1100,6 +1233,7 @@ async fn directory_tree_to_entrypoints_internal_untraced(
             &mut result,
             normalize_metadata_route(app_page)?,
             meta,
+            metadata_conflict_policy_value,
         );
     }
 
@@ -1119,6 +1253,7 @@ async fn directory_tree_to_entrypoints_internal_untraced(
                 &mut result,
                 normalize_metadata_route(app_page)?,
                 *meta,
+                metadata_conflict_policy_value,
             );
         }
 
@@ -1182,6 +1317,7 @@ async fn directory_tree_to_entrypoints_internal_untraced(
                 subdirectory,
                 child_app_page.clone(),
                 root_layouts,
+                metadata_conflict_policy,
             )
             .await?;
 
@@ -1248,7 +1384,13 @@ async fn directory_tree_to_entrypoints_internal_untraced(
                     add_app_route(app_dir, &mut result, page.clone(), path, root_layouts);
                 }
                 Entrypoint::AppMetadata { ref page, metadata } => {
-                    add_app_metadata_route(app_dir, &mut result, page.clone(), metadata);
+                    add_app_metadata_route(
+                        app_dir,
+                        &mut result,
+                        page.clone(),
+                        metadata,
+                        metadata_conflict_policy_value,
+                    );
                 }
             }
         }
@@ -1,12 +1,13 @@
 use anyhow::Result;
+use indexmap::IndexMap;
 use tracing::Instrument;
 use turbo_tasks::{
     graph::{AdjacencyMap, GraphTraversal},
-    Completion, Completions, TryFlatJoinIterExt, ValueToString, Vc,
+    Completion, Completions, RcStr, TryFlatJoinIterExt, ValueToString, Vc,
 };
-use turbo_tasks_fs::{rebase, FileSystemPath};
+use turbo_tasks_fs::{rebase, FileSystemPath, LinkContent, LinkType};
 use turbopack_core::{
-    asset::Asset,
+    asset::{Asset, AssetContent},
     output::{OutputAsset, OutputAssets},
 };
 
@@ -35,6 +36,10 @@ pub fn emit_all_assets(
 ///
 /// Assets inside the given client root are rebased to the given client output
 /// path.
+///
+/// Every asset is written by its own task, so all assets are written
+/// concurrently. Missing parent directories are created with
+/// `create_dir_all`, which succeeds when another write created them first.
 #[turbo_tasks::function]
 pub async fn emit_assets(
     assets: Vc<OutputAssets>,
@@ -90,6 +95,98 @@ async fn emit_rebase(
     Ok(content.resolve().await?.write(path.resolve().await?))
 }
 
+/// Maps the logical path of emitted assets, relative to the node root, to the
+/// content-addressed path they are stored at, relative to the node root too.
+#[turbo_tasks::value(transparent)]
+pub struct ContentAddressedPathMap(IndexMap<RcStr, RcStr>);
+
+/// Returns the logical path, content-addressed path and asset for every asset
+/// that [emit_assets] would emit.
+async fn content_addressed_entries(
+    assets: Vc<OutputAssets>,
+    node_root: Vc<FileSystemPath>,
+    client_relative_path: Vc<FileSystemPath>,
+    client_output_path: Vc<FileSystemPath>,
+) -> Result<Vec<(RcStr, RcStr, Vc<Box<dyn OutputAsset>>)>> {
+    let node_root_ref = &*node_root.await?;
+    let client_relative_path_ref = &*client_relative_path.await?;
+    assets
+        .await?
+        .iter()
+        .map(|&asset| async move {
+            let path = asset.ident().path();
+            let path_ref = path.await?;
+            let logical_path = if path_ref.is_inside_ref(node_root_ref) {
+                node_root_ref.get_path_to(&path_ref).map(RcStr::from)
+            } else if path_ref.is_inside_ref(client_relative_path_ref) {
+                let path = rebase(path, client_relative_path, client_output_path).await?;
+                node_root_ref.get_path_to(&path).map(RcStr::from)
+            } else {
+                None
+            };
+            let Some(logical_path) = logical_path else {
+                return Ok(None);
+            };
+            let AssetContent::File(file) = *asset.content().await? else {
+                return Ok(None);
+            };
+            let content_path = format!("content/{:016x}", *file.hash().await?).into();
+            Ok(Some((logical_path, content_path, asset)))
+        })
+        .try_flat_join()
+        .await
+}
+
+/// Computes where [emit_content_addressed_assets] stores each of the given
+/// assets.
+#[turbo_tasks::function]
+pub async fn content_addressed_path_map(
+    assets: Vc<OutputAssets>,
+    node_root: Vc<FileSystemPath>,
+    client_relative_path: Vc<FileSystemPath>,
+    client_output_path: Vc<FileSystemPath>,
+) -> Result<Vc<ContentAddressedPathMap>> {
+    Ok(Vc::cell(
+        content_addressed_entries(assets, node_root, client_relative_path, client_output_path)
+            .await?
+            .into_iter()
+            .map(|(logical_path, content_path, _)| (logical_path, content_path))
+            .collect(),
+    ))
+}
+
+/// Like [emit_assets], but stores every asset at `content/<hash>` in the node
+/// root, where `<hash>` is the hash of its content. The regular path of every
+/// asset is a relative symlink to its content-addressed path, so paths listed
+/// in manifests still resolve.
+#[turbo_tasks::function]
+pub async fn emit_content_addressed_assets(
+    assets: Vc<OutputAssets>,
+    node_root: Vc<FileSystemPath>,
+    client_relative_path: Vc<FileSystemPath>,
+    client_output_path: Vc<FileSystemPath>,
+) -> Result<Vc<Completion>> {
+    Ok(Vc::<Completions>::cell(
+        content_addressed_entries(assets, node_root, client_relative_path, client_output_path)
+            .await?
+            .into_iter()
+            .flat_map(|(logical_path, content_path, asset)| {
+                let depth = logical_path.matches('/').count();
+                let link = LinkContent::Link {
+                    target: format!("{}{content_path}", "../".repeat(depth)).into(),
+                    link_type: LinkType::empty(),
+                }
+                .cell();
+                [
+                    asset.content().write(node_root.join(content_path)),
+                    node_root.join(logical_path).write_link(link),
+                ]
+            })
+            .collect(),
+    )
+    .completed())
+}
+
 /// Walks the asset graph from multiple assets and collect all referenced
 /// assets.
 #[turbo_tasks::function]
@@ -41,7 +41,10 @@ pub mod util;
 pub use app_segment_config::{
     parse_segment_config_from_loader_tree, parse_segment_config_from_source,
 };
-pub use emit::{all_assets_from_entries, emit_all_assets, emit_assets};
+pub use emit::{
+    all_assets_from_entries, content_addressed_path_map, emit_all_assets, emit_assets,
+    emit_content_addressed_assets, ContentAddressedPathMap,
+};
 pub use next_edge::context::{
     get_edge_chunking_context, get_edge_chunking_context_with_client_assets,
     get_edge_compile_time_info, get_edge_resolve_options_context,
@@ -47,7 +47,8 @@ pub async fn get_app_page_entry(
 
     let server_component_transition = Vc::upcast(NextServerComponentTransition::new());
 
-    let base_path = next_config.await?.base_path.clone();
+    // Only depend on the base path, so unrelated config changes don't rebuild the loader tree.
+    let base_path = next_config.base_path().await?.clone_value();
     let loader_tree = LoaderTreeModule::build(
         loader_tree,
         module_asset_context,
@@ -0,0 +1,94 @@
+use anyhow::Result;
+use swc_core::{
+    common::GLOBALS,
+    ecma::ast::{Decl, ExportSpecifier, ModuleDecl, ModuleExportName, Program},
+};
+use turbo_tasks::{RcStr, Value, Vc};
+use turbopack_core::source::Source;
+use turbopack_ecmascript::{
+    parse::{parse, ParseResult},
+    EcmascriptInputTransforms, EcmascriptModuleAssetType,
+};
+
+/// The HTTP methods a route handler can export a handler for.
+pub const HTTP_METHODS: [&str; 7] = ["GET", "HEAD", "OPTIONS", "POST", "PUT", "DELETE", "PATCH"];
+
+/// The HTTP methods a route handler exports, in the order of [HTTP_METHODS].
+#[turbo_tasks::value(transparent)]
+pub struct AppRouteMethods(Vec<RcStr>);
+
+/// Returns the HTTP methods exported by the route handler `source`.
+#[turbo_tasks::function]
+pub async fn get_app_route_methods(source: Vc<Box<dyn Source>>) -> Result<Vc<AppRouteMethods>> {
+    let path = source.ident().path().await?;
+
+    let ty = if path.path.ends_with(".d.ts") {
+        return Ok(Vc::cell(vec![]));
+    } else if path.path.ends_with(".ts") {
+        EcmascriptModuleAssetType::Typescript {
+            tsx: false,
+            analyze_types: false,
+        }
+    } else if path.path.ends_with(".tsx") {
+        EcmascriptModuleAssetType::Typescript {
+            tsx: true,
+            analyze_types: false,
+        }
+    } else if path.path.ends_with(".js") || path.path.ends_with(".jsx") {
+        EcmascriptModuleAssetType::Ecmascript
+    } else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let result = &*parse(source, Value::new(ty), EcmascriptInputTransforms::empty()).await?;
+
+    let ParseResult::Ok {
+        program: Program::Module(module_ast),
+        globals,
+        ..
+    } = result
+    else {
+        return Ok(Vc::cell(vec![]));
+    };
+
+    let exports = GLOBALS.set(globals, || {
+        let mut exports = vec![];
+        for item in &module_ast.body {
+            match item.as_module_decl() {
+                Some(ModuleDecl::ExportDecl(export_decl)) => match &export_decl.decl {
+                    Decl::Fn(fn_decl) => exports.push(fn_decl.ident.sym.to_string()),
+                    Decl::Var(var_decl) => exports.extend(
+                        var_decl
+                            .decls
+                            .iter()
+                            .filter_map(|decl| decl.name.as_ident())
+                            .map(|ident| ident.sym.to_string()),
+                    ),
+                    _ => {}
+                },
+                Some(ModuleDecl::ExportNamed(named_export)) => {
+                    for specifier in &named_export.specifiers {
+                        let ExportSpecifier::Named(specifier) = specifier else {
+                            continue;
+                        };
+                        if let ModuleExportName::Ident(ident) =
+                            specifier.exported.as_ref().unwrap_or(&specifier.orig)
+                        {
+                            exports.push(ident.sym.to_string());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        exports
+    });
+
+    Ok(Vc::cell(
+        HTTP_METHODS
+            .into_iter()
+            .filter(|method| exports.iter().any(|export| export == method))
+            .map(RcStr::from)
+            .collect(),
+    ))
+}
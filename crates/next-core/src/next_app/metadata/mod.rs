@@ -5,7 +5,10 @@ use once_cell::sync::Lazy;
 use turbo_tasks::{RcStr, Vc};
 use turbo_tasks_fs::FileSystemPath;
 
-use crate::next_app::{AppPage, PageSegment, PageType};
+use crate::{
+    app_structure::MetadataItem,
+    next_app::{AppPage, PageSegment, PageType},
+};
 
 pub mod image;
 pub mod route;
@@ -81,7 +84,42 @@ fn match_metadata_file<'a>(
     })
 }
 
+/// Resolves the content type of metadata items that don't follow one of the
+/// built-in metadata file conventions.
+#[turbo_tasks::value_trait]
+pub trait MetadataContentTypeResolver {
+    /// Returns `None` if the resolver doesn't know the content type of
+    /// `metadata`.
+    fn content_type(self: Vc<Self>, metadata: MetadataItem) -> Vc<Option<RcStr>>;
+}
+
 pub(crate) async fn get_content_type(path: Vc<FileSystemPath>) -> Result<String> {
+    Ok(get_builtin_content_type(path)
+        .await?
+        .unwrap_or_else(|| "text/plain".to_string()))
+}
+
+/// Like [get_content_type], but consults `resolver` for items that aren't
+/// built-in metadata types before falling back to `text/plain`.
+pub(crate) async fn resolve_content_type(
+    metadata: MetadataItem,
+    resolver: Option<Vc<Box<dyn MetadataContentTypeResolver>>>,
+) -> Result<String> {
+    let path = match metadata {
+        MetadataItem::Static { path } | MetadataItem::Dynamic { path } => path,
+    };
+    if let Some(content_type) = get_builtin_content_type(path).await? {
+        return Ok(content_type);
+    }
+    if let Some(resolver) = resolver {
+        if let Some(content_type) = &*resolver.content_type(metadata).await? {
+            return Ok(content_type.to_string());
+        }
+    }
+    Ok("text/plain".to_string())
+}
+
+async fn get_builtin_content_type(path: Vc<FileSystemPath>) -> Result<Option<String>> {
     let stem = &*path.file_stem().await?;
     let ext = &*path.extension().await?;
 
@@ -92,25 +130,27 @@ pub(crate) async fn get_content_type(path: Vc<FileSystemPath>) -> Result<String>
     }
 
     if name == "favicon" && ext == "ico" {
-        return Ok("image/x-icon".to_string());
+        return Ok(Some("image/x-icon".to_string()));
     }
     if name == "sitemap" {
-        return Ok("application/xml".to_string());
+        return Ok(Some("application/xml".to_string()));
     }
     if name == "robots" {
-        return Ok("text/plain".to_string());
+        return Ok(Some("text/plain".to_string()));
     }
     if name == "manifest" {
-        return Ok("application/manifest+json".to_string());
+        return Ok(Some("application/manifest+json".to_string()));
     }
 
     if ext == "png" || ext == "jpeg" || ext == "ico" || ext == "svg" {
-        return Ok(mime_guess::from_ext(ext)
-            .first_or_octet_stream()
-            .to_string());
+        return Ok(Some(
+            mime_guess::from_ext(ext)
+                .first_or_octet_stream()
+                .to_string(),
+        ));
     }
 
-    Ok("text/plain".to_string())
+    Ok(None)
 }
 
 pub fn match_local_metadata_file<'a>(
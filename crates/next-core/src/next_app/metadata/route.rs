@@ -13,7 +13,7 @@ use turbopack_core::{
 };
 use turbopack_ecmascript::utils::StringifyJs;
 
-use super::get_content_type;
+use super::{get_content_type, resolve_content_type, MetadataContentTypeResolver};
 use crate::{
     app_structure::MetadataItem,
     mode::NextMode,
@@ -30,9 +30,10 @@ pub async fn get_app_metadata_route_source(
     mode: NextMode,
     metadata: MetadataItem,
     is_multi_dynamic: bool,
+    content_type_resolver: Option<Vc<Box<dyn MetadataContentTypeResolver>>>,
 ) -> Result<Vc<Box<dyn Source>>> {
     Ok(match metadata {
-        MetadataItem::Static { path } => static_route_source(mode, path),
+        MetadataItem::Static { path } => static_route_source(mode, path, content_type_resolver),
         MetadataItem::Dynamic { path } => {
             let stem = path.file_stem().await?;
             let stem = stem.as_deref().unwrap_or_default();
@@ -57,6 +58,7 @@ pub async fn get_app_metadata_route_entry(
     mode: NextMode,
     metadata: MetadataItem,
     next_config: Vc<NextConfig>,
+    content_type_resolver: Option<Vc<Box<dyn MetadataContentTypeResolver>>>,
 ) -> Vc<AppEntry> {
     // Read original source's segment config before replacing source into
     // dynamic|static metadata route handler.
@@ -102,7 +104,7 @@ pub async fn get_app_metadata_route_entry(
     get_app_route_entry(
         nodejs_context,
         edge_context,
-        get_app_metadata_route_source(mode, metadata, is_multi_dynamic),
+        get_app_metadata_route_source(mode, metadata, is_multi_dynamic, content_type_resolver),
         page,
         project_root,
         Some(segment_config),
@@ -132,11 +134,13 @@ async fn get_base64_file_content(path: Vc<FileSystemPath>) -> Result<String> {
 async fn static_route_source(
     mode: NextMode,
     path: Vc<FileSystemPath>,
+    content_type_resolver: Option<Vc<Box<dyn MetadataContentTypeResolver>>>,
 ) -> Result<Vc<Box<dyn Source>>> {
     let stem = path.file_stem().await?;
     let stem = stem.as_deref().unwrap_or_default();
 
-    let content_type = get_content_type(path).await?;
+    let content_type =
+        resolve_content_type(MetadataItem::Static { path }, content_type_resolver).await?;
 
     let cache_control = if stem == "favicon" {
         CACHE_HEADER_REVALIDATE
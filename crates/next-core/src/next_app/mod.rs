@@ -3,6 +3,7 @@ pub mod app_client_shared_chunks;
 pub mod app_entry;
 pub mod app_page_entry;
 pub mod app_route_entry;
+pub mod app_route_methods;
 pub mod include_modules_module;
 pub mod metadata;
 
@@ -23,6 +24,7 @@ pub use crate::next_app::{
     app_page_entry::get_app_page_entry,
     app_route_entry::get_app_route_entry,
 };
+use crate::next_edge::route_regex::INTERCEPTION_ROUTE_MARKERS;
 
 /// See [AppPage].
 #[derive(
@@ -259,6 +261,28 @@ impl AppPage {
         )
     }
 
+    /// Whether a segment of the page intercepts another route, e.g.
+    /// `/feed/(..)photo/[id]/page`.
+    pub fn is_interception_route(&self) -> bool {
+        self.0.iter().any(|segment| {
+            matches!(segment, PageSegment::Static(segment) if INTERCEPTION_ROUTE_MARKERS
+                .iter()
+                .any(|marker| segment.starts_with(marker)))
+        })
+    }
+
+    /// The names of the route groups the page is in, outermost first, e.g.
+    /// `["marketing"]` for `/(marketing)/about/page`.
+    pub fn route_groups(&self) -> Vec<RcStr> {
+        self.0
+            .iter()
+            .filter_map(|segment| match segment {
+                PageSegment::Group(group) => Some(group.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub fn complete(&self, page_type: PageType) -> Result<Self> {
         self.clone_push(PageSegment::PageType(page_type))
     }
@@ -467,3 +491,49 @@ impl From<AppPage> for AppPath {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use turbo_tasks::RcStr;
+
+    use super::AppPage;
+
+    #[test]
+    fn test_is_interception_route() {
+        for page in [
+            "/feed/(.)photo/[id]/page",
+            "/feed/(..)photo/[id]/page",
+            "/feed/(..)(..)photo/[id]/page",
+            "/@modal/(...)photo/[id]/page",
+        ] {
+            assert!(
+                AppPage::parse(page).unwrap().is_interception_route(),
+                "{page}"
+            );
+        }
+        for page in [
+            "/photo/[id]/page",
+            "/(group)/photo/page",
+            "/@modal/photo/page",
+        ] {
+            assert!(
+                !AppPage::parse(page).unwrap().is_interception_route(),
+                "{page}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_route_groups() {
+        assert_eq!(
+            AppPage::parse("/(marketing)/(en)/about/page")
+                .unwrap()
+                .route_groups(),
+            vec![RcStr::from("marketing"), RcStr::from("en")]
+        );
+        assert!(AppPage::parse("/about/[slug]/page")
+            .unwrap()
+            .route_groups()
+            .is_empty());
+    }
+}
@@ -14,7 +14,7 @@ use turbopack::{
 };
 use turbopack_browser::{react_refresh::assert_can_resolve_react_refresh, BrowserChunkingContext};
 use turbopack_core::{
-    chunk::{module_id_strategies::ModuleIdStrategy, ChunkingContext},
+    chunk::{module_id_strategies::ModuleIdStrategy, ChunkSizeThresholds, ChunkingContext},
     compile_time_info::{
         CompileTimeDefineValue, CompileTimeDefines, CompileTimeInfo, DefineableNameSegment,
         FreeVarReference, FreeVarReferences,
@@ -354,10 +354,12 @@ pub async fn get_client_module_options_context(
 pub async fn get_client_chunking_context(
     project_path: Vc<FileSystemPath>,
     client_root: Vc<FileSystemPath>,
+    client_assets_path: Vc<FileSystemPath>,
     asset_prefix: Vc<Option<RcStr>>,
     environment: Vc<Environment>,
     mode: Vc<NextMode>,
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    chunk_size_thresholds: Vc<ChunkSizeThresholds>,
 ) -> Result<Vc<Box<dyn ChunkingContext>>> {
     let next_mode = mode.await?;
     let mut builder = BrowserChunkingContext::builder(
@@ -365,14 +367,15 @@ pub async fn get_client_chunking_context(
         client_root,
         client_root,
         client_root.join("static/chunks".into()),
-        get_client_assets_path(client_root),
+        client_assets_path,
         environment,
         next_mode.runtime_type(),
     )
     .chunk_base_path(asset_prefix)
     .minify_type(next_mode.minify_type())
     .asset_base_path(asset_prefix)
-    .module_id_strategy(module_id_strategy);
+    .module_id_strategy(module_id_strategy)
+    .chunk_size_thresholds(*chunk_size_thresholds.await?);
 
     if next_mode.is_development() {
         builder = builder.hot_module_replacement();
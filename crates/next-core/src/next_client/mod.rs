@@ -3,7 +3,8 @@ pub(crate) mod runtime_entry;
 pub(crate) mod transforms;
 
 pub use context::{
-    get_client_chunking_context, get_client_compile_time_info, get_client_module_options_context,
-    get_client_resolve_options_context, get_client_runtime_entries, ClientContextType,
+    get_client_assets_path, get_client_chunking_context, get_client_compile_time_info,
+    get_client_module_options_context, get_client_resolve_options_context,
+    get_client_runtime_entries, ClientContextType,
 };
 pub use runtime_entry::{RuntimeEntries, RuntimeEntry};
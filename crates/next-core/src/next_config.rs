@@ -11,6 +11,7 @@ use turbopack::module_options::{
     module_options_context::MdxTransformOptions, LoaderRuleItem, OptionWebpackRules,
 };
 use turbopack_core::{
+    chunk::ChunkSizeThresholds,
     issue::{Issue, IssueSeverity, IssueStage, OptionStyledString, StyledString},
     resolve::ResolveAliasMap,
 };
@@ -224,7 +225,7 @@ pub enum RouteHas {
     },
 }
 
-#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct HeaderValue {
     pub key: RcStr,
@@ -401,6 +402,115 @@ pub struct ExperimentalTurboConfig {
     pub use_swc_css: Option<bool>,
     pub tree_shaking: Option<bool>,
     pub module_id_strategy: Option<ModuleIdStrategy>,
+    /// Reports cycles between client references found while building app
+    /// pages. `"fail"` additionally fails the build.
+    pub client_reference_cycles: Option<ClientReferenceCyclesMode>,
+    /// Moves the dependencies of app server entries into separate chunks
+    /// instead of bundling them into the entry chunk.
+    pub split_server_entry: Option<bool>,
+    /// Seeds the chunk groups of app endpoints from chunks shared by all
+    /// endpoints, so common modules aren't duplicated across endpoints.
+    pub shared_server_base: Option<bool>,
+    /// Emits an OpenAPI stub listing the exported HTTP methods of every app
+    /// route handler.
+    pub route_openapi_stubs: Option<bool>,
+    /// Emits a `dynamic-imports-manifest.json` for each app route, which lists
+    /// the chunks of each dynamic import for preloading.
+    pub dynamic_imports_manifest: Option<bool>,
+    /// Percent-encodes characters in app route names that aren't allowed in
+    /// file names on all platforms, e.g. `:` and `*`, when building output
+    /// paths.
+    pub sanitize_route_paths: Option<bool>,
+    /// Stores emitted files at `content/<hash>` in the output directory and
+    /// links their regular path to it.
+    pub content_addressed_output: Option<bool>,
+    /// Logs the approximate memory usage of building each app route, split by
+    /// build phase. Only meant for debugging.
+    pub endpoint_memory_report: Option<bool>,
+    /// Doesn't emit `polyfill-nomodule.js` for app routes.
+    pub disable_polyfill: Option<bool>,
+    /// Only builds the RSC payload of app pages, so the HTML endpoint of a page
+    /// builds the same output as its RSC endpoint.
+    pub rsc_only: Option<bool>,
+    /// Reports an error for every Node.js built-in module imported by an app
+    /// route using the edge runtime. Enabled unless set to `false`.
+    pub validate_edge_node_builtins: Option<bool>,
+    /// The format of the react-loadable manifest emitted for every endpoint.
+    pub loadable_manifest_format: Option<LoadableManifestFormat>,
+    /// The total module size in bytes below which client and server chunks are
+    /// merged with other small chunks.
+    pub min_chunk_size: Option<usize>,
+    /// The total module size in bytes above which client and server chunks are
+    /// split further.
+    pub max_chunk_size: Option<usize>,
+    /// Headers to attach to the responses of app routes, keyed by the page name
+    /// of the route, e.g. `/blog/[slug]/page`.
+    pub route_headers: Option<IndexMap<RcStr, Vec<HeaderValue>>>,
+    /// The file name of the server actions manifest, without extension.
+    pub server_actions_manifest_name: Option<RcStr>,
+    /// Reports modules that are bundled into more than one client chunk of an
+    /// app page.
+    pub report_duplicate_client_modules: Option<bool>,
+    /// Emits gzip-compressed manifests with a `.json.gz` extension instead of
+    /// plain JSON manifests.
+    pub compress_manifests: Option<bool>,
+    /// Emits a `stats.json` next to the manifests of each app route, listing
+    /// the sizes of its chunks and their largest modules.
+    pub analyze: Option<bool>,
+    /// Sorts the `rootMainFiles` of the build manifest by path, so their order
+    /// doesn't depend on chunking.
+    pub sort_root_main_files: Option<bool>,
+    /// Emits an `env-manifest.json` next to the manifests of each app page,
+    /// listing the `NEXT_PUBLIC_` variables inlined into its client bundle.
+    pub env_manifest: Option<bool>,
+    /// Reports failures to resolve critical runtime modules, like the client
+    /// main module, as fatal issues instead of errors.
+    pub strict_runtime_resolution: Option<bool>,
+    /// Chunks each app route handler running on Node.js without splitting
+    /// its chunk groups by size and without sharing chunks with other
+    /// endpoints.
+    pub unsplit_route_handlers: Option<bool>,
+    /// Modules to expose as module federation remotes, keyed by the name they
+    /// are exposed as. The values are file paths relative to the project
+    /// directory. The modules are chunked for the client and listed in the
+    /// `federation-manifest.json` of the app directory.
+    pub federation_exposes: Option<IndexMap<RcStr, RcStr>>,
+    /// Makes writing an endpoint to disk fail when building it emitted an
+    /// issue of this severity or a more severe one.
+    pub failing_issue_severity: Option<IssueSeverity>,
+    /// Adds debug IDs to the JavaScript chunks of app endpoints and their
+    /// source maps.
+    pub debug_ids: Option<bool>,
+    /// Writes manifests as compact instead of pretty-printed JSON. Defaults to
+    /// compact JSON in builds and pretty-printed JSON in development.
+    pub compact_manifests: Option<bool>,
+    /// Module specifiers that are evaluated by the server entry chunk of every
+    /// Node.js app endpoint, so that they are loaded on startup.
+    pub server_entry_modules: Option<Vec<RcStr>>,
+    /// Lets edge endpoints load the SSR chunks of client components on demand
+    /// instead of listing them in the middleware manifest.
+    pub edge_chunk_loading: Option<bool>,
+    /// Which names the entries of `app-paths-manifest.json` are keyed by.
+    pub app_paths_manifest_key: Option<AppPathsManifestKey>,
+    /// The total size in bytes of the files of an edge function above which an
+    /// error is reported for it.
+    pub edge_function_size_limit: Option<usize>,
+    /// Emits client assets, e.g. images, into the client chunks directory, so
+    /// all client files are in a single flat directory.
+    pub flat_client_assets: Option<bool>,
+    /// Merges the app build manifest of each app page into its build manifest
+    /// instead of emitting `app-build-manifest.json`.
+    pub unified_build_manifest: Option<bool>,
+    /// Additionally emits the middleware manifest of each app route using the
+    /// edge runtime in the legacy v1 format, as `legacy-middleware-manifest.json`
+    /// next to `middleware-manifest.json`.
+    pub legacy_middleware_manifest: Option<bool>,
+    /// Emits `sources-manifest.json` for each app route, which lists the source
+    /// files of all modules the route is built from. Walking the whole module
+    /// graph is expensive, so this is off by default.
+    pub sources_manifest: Option<bool>,
+    /// What happens when several metadata files resolve to the same route.
+    pub metadata_conflict_policy: Option<MetadataConflictPolicy>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
@@ -444,6 +554,19 @@ pub enum ModuleIdStrategy {
 #[turbo_tasks::value(transparent)]
 pub struct OptionModuleIdStrategy(pub Option<ModuleIdStrategy>);
 
+#[turbo_tasks::value]
+#[derive(Clone, Copy, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum ClientReferenceCyclesMode {
+    /// Emit an error issue for every cycle.
+    Error,
+    /// Emit an error issue for every cycle and fail the build.
+    Fail,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionClientReferenceCyclesMode(pub Option<ClientReferenceCyclesMode>);
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(untagged)]
 pub enum MdxRsOptions {
@@ -771,6 +894,64 @@ pub struct ResolveExtensions(Option<Vec<RcStr>>);
 #[turbo_tasks::value(transparent)]
 pub struct OptionalMdxTransformOptions(Option<Vc<MdxTransformOptions>>);
 
+#[turbo_tasks::value]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum LoadableManifestFormat {
+    /// A flat map from import id to the files it loads.
+    #[default]
+    Classic,
+    /// Imports grouped by importing module, see
+    /// [crate::next_manifests::StreamingLoadableManifest].
+    Streaming,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct RouteHeaders(Vec<HeaderValue>);
+
+/// See [ExperimentalTurboConfig::federation_exposes].
+#[turbo_tasks::value(transparent)]
+pub struct FederationExposes(IndexMap<RcStr, RcStr>);
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionIssueSeverity(Option<IssueSeverity>);
+
+/// The names the entries of `app-paths-manifest.json` are keyed by. The
+/// original name of a route includes route groups, parallel route slots,
+/// interception markers and the `/page` or `/route` suffix, e.g.
+/// `/(shop)/@modal/(.)cart/page`, while its pathname is the URL path it
+/// matches, e.g. `/cart`.
+#[turbo_tasks::value]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum AppPathsManifestKey {
+    /// Key entries by the original name of the route.
+    #[default]
+    OriginalName,
+    /// Key entries by the pathname of the route.
+    Pathname,
+    /// Add entries for both the original name and the pathname.
+    Both,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionUsize(Option<usize>);
+
+/// How to handle several metadata files, e.g. `opengraph-image.png` and
+/// `opengraph-image.tsx`, that resolve to the same metadata route.
+#[turbo_tasks::value]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum MetadataConflictPolicy {
+    /// Report an error and keep the first file.
+    #[default]
+    Error,
+    /// Report a warning and keep the first file.
+    FirstWins,
+    /// Report a warning and keep the last file.
+    LastWins,
+}
+
 #[turbo_tasks::value_impl]
 impl NextConfig {
     #[turbo_tasks::function]
@@ -791,6 +972,11 @@ impl NextConfig {
         Vc::cell(self.react_production_profiling.unwrap_or_default())
     }
 
+    #[turbo_tasks::function]
+    pub fn base_path(&self) -> Vc<Option<RcStr>> {
+        Vc::cell(self.base_path.clone())
+    }
+
     #[turbo_tasks::function]
     pub async fn server_external_packages(self: Vc<Self>) -> Result<Vc<Vec<RcStr>>> {
         Ok(Vc::cell(
@@ -1167,6 +1353,441 @@ impl NextConfig {
         };
         Ok(Vc::cell(Some(module_id_strategy.clone())))
     }
+
+    #[turbo_tasks::function]
+    pub async fn client_reference_cycles(
+        self: Vc<Self>,
+    ) -> Result<Vc<OptionClientReferenceCyclesMode>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.client_reference_cycles),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn split_server_entry(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.split_server_entry)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn shared_server_base(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.shared_server_base)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn route_openapi_stubs(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.route_openapi_stubs)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn dynamic_imports_manifest(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.dynamic_imports_manifest)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn sanitize_route_paths(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.sanitize_route_paths)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn content_addressed_output(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.content_addressed_output)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn endpoint_memory_report(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.endpoint_memory_report)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn disable_polyfill(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.disable_polyfill)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn rsc_only(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.rsc_only)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn validate_edge_node_builtins(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.validate_edge_node_builtins)
+                .unwrap_or(true),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn loadable_manifest_format(self: Vc<Self>) -> Result<Vc<LoadableManifestFormat>> {
+        Ok(self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|turbo| turbo.loadable_manifest_format)
+            .unwrap_or_default()
+            .cell())
+    }
+
+    #[turbo_tasks::function]
+    pub async fn chunk_size_thresholds(self: Vc<Self>) -> Result<Vc<ChunkSizeThresholds>> {
+        let this = self.await?;
+        let turbo = this.experimental.turbo.as_ref();
+        let defaults = ChunkSizeThresholds::default();
+        Ok(ChunkSizeThresholds {
+            min: turbo
+                .and_then(|turbo| turbo.min_chunk_size)
+                .unwrap_or(defaults.min),
+            max: turbo
+                .and_then(|turbo| turbo.max_chunk_size)
+                .unwrap_or(defaults.max),
+        }
+        .cell())
+    }
+
+    /// The headers configured for the app route with the page name
+    /// `original_name`.
+    #[turbo_tasks::function]
+    pub async fn route_headers(self: Vc<Self>, original_name: RcStr) -> Result<Vc<RouteHeaders>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.route_headers.as_ref())
+                .and_then(|route_headers| route_headers.get(&original_name))
+                .cloned()
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn server_actions_manifest_name(self: Vc<Self>) -> Result<Vc<RcStr>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.server_actions_manifest_name.clone())
+                .unwrap_or_else(|| "server-reference-manifest".into()),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn report_duplicate_client_modules(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.report_duplicate_client_modules)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn compress_manifests(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.compress_manifests)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn analyze(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.analyze)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn sort_root_main_files(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.sort_root_main_files)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn env_manifest(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.env_manifest)
+                .unwrap_or(false),
+        ))
+    }
+
+    /// The severity of issues for critical runtime modules that can't be
+    /// resolved.
+    #[turbo_tasks::function]
+    pub async fn runtime_resolve_severity(self: Vc<Self>) -> Result<Vc<IssueSeverity>> {
+        let strict = self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|turbo| turbo.strict_runtime_resolution)
+            .unwrap_or(false);
+        Ok(if strict {
+            IssueSeverity::Fatal.cell()
+        } else {
+            IssueSeverity::Error.cell()
+        })
+    }
+
+    #[turbo_tasks::function]
+    pub async fn unsplit_route_handlers(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.unsplit_route_handlers)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn flat_client_assets(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.flat_client_assets)
+                .unwrap_or(false),
+        ))
+    }
+
+    /// Whether manifests are written as compact JSON. Unless configured, they
+    /// are pretty-printed in development only.
+    #[turbo_tasks::function]
+    pub async fn compact_manifests(self: Vc<Self>, is_development: bool) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.compact_manifests)
+                .unwrap_or(!is_development),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn federation_exposes(self: Vc<Self>) -> Result<Vc<FederationExposes>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.federation_exposes.clone())
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn failing_issue_severity(self: Vc<Self>) -> Result<Vc<OptionIssueSeverity>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.failing_issue_severity),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn debug_ids(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.debug_ids)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn server_entry_modules(self: Vc<Self>) -> Result<Vc<Vec<RcStr>>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.server_entry_modules.clone())
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn edge_chunk_loading(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.edge_chunk_loading)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn app_paths_manifest_key(self: Vc<Self>) -> Result<Vc<AppPathsManifestKey>> {
+        Ok(self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|turbo| turbo.app_paths_manifest_key)
+            .unwrap_or_default()
+            .cell())
+    }
+
+    #[turbo_tasks::function]
+    pub async fn edge_function_size_limit(self: Vc<Self>) -> Result<Vc<OptionUsize>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.edge_function_size_limit),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn unified_build_manifest(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.unified_build_manifest)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn legacy_middleware_manifest(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.legacy_middleware_manifest)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn sources_manifest(self: Vc<Self>) -> Result<Vc<bool>> {
+        Ok(Vc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|turbo| turbo.sources_manifest)
+                .unwrap_or(false),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn metadata_conflict_policy(self: Vc<Self>) -> Result<Vc<MetadataConflictPolicy>> {
+        Ok(self
+            .await?
+            .experimental
+            .turbo
+            .as_ref()
+            .and_then(|turbo| turbo.metadata_conflict_policy)
+            .unwrap_or_default()
+            .cell())
+    }
 }
 
 /// A subset of ts/jsconfig that next.js implicitly
@@ -180,6 +180,7 @@ pub async fn get_edge_chunking_context_with_client_assets(
     project_path: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     client_root: Vc<FileSystemPath>,
+    client_assets_path: Vc<FileSystemPath>,
     asset_prefix: Vc<Option<RcStr>>,
     environment: Vc<Environment>,
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
@@ -192,7 +193,7 @@ pub async fn get_edge_chunking_context_with_client_assets(
             output_root,
             client_root,
             output_root.join("chunks/ssr".into()),
-            client_root.join("static/media".into()),
+            client_assets_path,
             environment,
             next_mode.runtime_type(),
         )
@@ -6,7 +6,8 @@ use std::collections::HashMap;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-const INTERCEPTION_ROUTE_MARKERS: [&str; 4] = ["(..)(..)", "(.)", "(..)", "(...)"];
+/// The prefixes of segments that intercept another route, e.g. `(.)photo`.
+pub const INTERCEPTION_ROUTE_MARKERS: [&str; 4] = ["(..)(..)", "(.)", "(..)", "(...)"];
 const NEXT_QUERY_PARAM_PREFIX: &str = "nxtP";
 const NEXT_INTERCEPTION_MARKER_PREFIX: &str = "nxtI";
 
@@ -265,3 +266,102 @@ pub fn get_named_middleware_regex(normalized_route: &str) -> String {
     let (parameterized_route, _route_keys) = get_named_parametrized_route(normalized_route, true);
     format!("^{}(?:/)?$", parameterized_route)
 }
+
+/// Prefixes `pathname` with the configured `basePath`. The root pathname maps
+/// to the base path itself, as that's where Next.js serves it.
+pub fn with_base_path(base_path: &str, pathname: &str) -> String {
+    let base_path = base_path.trim_end_matches('/');
+    if base_path.is_empty() {
+        pathname.to_string()
+    } else if pathname == "/" {
+        base_path.to_string()
+    } else {
+        format!("{base_path}{pathname}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::{get_named_middleware_regex, get_route_regex, with_base_path};
+
+    #[test]
+    fn named_and_positional_middleware_regex() {
+        let route = "/blog/[slug]/[[...rest]]";
+
+        assert_eq!(
+            get_named_middleware_regex(route),
+            r"^/blog/(?P<nxtPslug>[^/]+?)(?:/(?P<nxtPrest>.+?))?(?:/)?$"
+        );
+        assert_eq!(
+            get_route_regex(route).regex,
+            r"^/blog/([^/]+?)(?:/(.+?))?(?:/)?$"
+        );
+    }
+
+    #[test]
+    fn middleware_regex_of_static_route() {
+        let route = "/about";
+
+        assert_eq!(get_named_middleware_regex(route), r"^/about(?:/)?$");
+        assert_eq!(
+            get_route_regex(route).regex,
+            get_named_middleware_regex(route)
+        );
+    }
+
+    /// With `trailingSlash: false` the canonical pathname has no trailing
+    /// slash, but the matcher must still match the variant with one.
+    #[test]
+    fn middleware_matcher_without_trailing_slash() {
+        let regex = Regex::new(&get_named_middleware_regex("/blog/[slug]")).unwrap();
+
+        assert!(regex.is_match("/blog/hello"));
+        assert!(regex.is_match("/blog/hello/"));
+        assert!(!regex.is_match("/blog"));
+    }
+
+    /// With `trailingSlash: true` the canonical pathname can end with a slash,
+    /// which must not make the matcher reject the variant without one.
+    #[test]
+    fn middleware_matcher_with_trailing_slash() {
+        let regex = Regex::new(&get_named_middleware_regex("/blog/[slug]/")).unwrap();
+
+        assert!(regex.is_match("/blog/hello"));
+        assert!(regex.is_match("/blog/hello/"));
+        assert!(!regex.is_match("/blog"));
+        assert_eq!(
+            get_named_middleware_regex("/blog/[slug]/"),
+            get_named_middleware_regex("/blog/[slug]")
+        );
+    }
+
+    #[test]
+    fn empty_base_path() {
+        assert_eq!(with_base_path("", "/blog/[slug]"), "/blog/[slug]");
+        assert_eq!(with_base_path("", "/"), "/");
+    }
+
+    #[test]
+    fn middleware_matcher_with_base_path() {
+        let pathname = with_base_path("/docs", "/blog/[slug]");
+        assert_eq!(pathname, "/docs/blog/[slug]");
+
+        let regex = Regex::new(&get_named_middleware_regex(&pathname)).unwrap();
+        assert!(regex.is_match("/docs/blog/hello"));
+        assert!(regex.is_match("/docs/blog/hello/"));
+        assert!(!regex.is_match("/blog/hello"));
+    }
+
+    #[test]
+    fn middleware_matcher_of_root_with_base_path() {
+        let pathname = with_base_path("/docs/", "/");
+        assert_eq!(pathname, "/docs");
+
+        let regex = Regex::new(&get_named_middleware_regex(&pathname)).unwrap();
+        assert!(regex.is_match("/docs"));
+        assert!(regex.is_match("/docs/"));
+        assert!(!regex.is_match("/"));
+    }
+}
@@ -1,9 +1,11 @@
 use anyhow::Result;
 use indoc::formatdoc;
-use turbo_tasks::Vc;
+use turbo_tasks::{RcStr, Vc};
 use turbo_tasks_fs::{File, FileSystemPath};
 use turbopack_core::{
     asset::AssetContent,
+    ident::AssetIdent,
+    module::Module,
     resolve::{
         options::{ImportMapResult, ImportMappingReplacement, ReplacedImportMapping},
         parse::Request,
@@ -14,6 +16,23 @@ use turbopack_core::{
 };
 use turbopack_node::execution_context::ExecutionContext;
 
+/// Prefix of the ident modifier of modules replacing an unsupported module.
+const UNSUPPORTED_MODULE_MODIFIER_PREFIX: &str = "unsupported module ";
+
+/// Returns the name of the unsupported module that `module` replaces, if it
+/// was created by [NextEdgeUnsupportedModuleReplacer].
+pub async fn get_unsupported_module_name(module: Vc<Box<dyn Module>>) -> Result<Option<RcStr>> {
+    for modifier in module.ident().await?.modifiers.iter() {
+        if let Some(name) = modifier
+            .await?
+            .strip_prefix(UNSUPPORTED_MODULE_MODIFIER_PREFIX)
+        {
+            return Ok(Some(name.into()));
+        }
+    }
+    Ok(None)
+}
+
 /// Intercepts requests for the given request to `unsupported` error messages
 /// by returning a VirtualSource proxies to any import request to raise a
 /// runtime error.
@@ -62,7 +81,12 @@ impl ImportMappingReplacement for NextEdgeUnsupportedModuleReplacer {
               "#
             };
             let content = AssetContent::file(File::from(code).into());
-            let source = VirtualSource::new(root_path, content);
+            // The modifier keeps replacements of different modules apart and allows to find
+            // them in the module graph.
+            let ident = AssetIdent::from_path(root_path).with_modifier(Vc::cell(
+                format!("{UNSUPPORTED_MODULE_MODIFIER_PREFIX}{module}").into(),
+            ));
+            let source = VirtualSource::new_with_ident(ident, content);
             return Ok(
                 ImportMapResult::Result(ResolveResult::source(Vc::upcast(source)).into()).into(),
             );
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use indexmap::IndexSet;
 use indoc::formatdoc;
 use turbo_tasks::{RcStr, TryJoinIterExt, ValueToString, Vc};
 use turbo_tasks_fs::{File, FileSystemPath};
@@ -13,7 +14,10 @@ use turbopack_core::{
 };
 use turbopack_ecmascript::utils::StringifyJs;
 
-use super::{ClientReferenceManifest, ManifestNode, ManifestNodeEntry, ModuleId};
+use super::{
+    transform_asset_path, AssetPathTransform, ClientReferenceManifest, ManifestNode,
+    ManifestNodeEntry, ModuleId,
+};
 use crate::{
     next_app::ClientReferencesChunks,
     next_client_reference::{ClientReferenceGraphResult, ClientReferenceType},
@@ -28,12 +32,14 @@ impl ClientReferenceManifest {
         node_root: Vc<FileSystemPath>,
         client_relative_path: Vc<FileSystemPath>,
         entry_name: RcStr,
+        manifest_path_prefix: RcStr,
         client_references: Vc<ClientReferenceGraphResult>,
         client_references_chunks: Vc<ClientReferencesChunks>,
         client_chunking_context: Vc<Box<dyn ChunkingContext>>,
         ssr_chunking_context: Option<Vc<Box<dyn ChunkingContext>>>,
         next_config: Vc<NextConfig>,
         runtime: NextRuntime,
+        asset_path_transform: Option<Vc<Box<dyn AssetPathTransform>>>,
     ) -> Result<Vc<Box<dyn OutputAsset>>> {
         let mut entry_manifest: ClientReferenceManifest = Default::default();
         entry_manifest.module_loading.prefix = next_config
@@ -55,6 +61,44 @@ impl ClientReferenceManifest {
         for app_client_reference in client_references.await?.client_references.iter() {
             let app_client_reference_ty = app_client_reference.ty();
 
+            // CSS of a client reference is preloaded together with the server component
+            // importing it
+            if let (ClientReferenceType::CssClientReference(_), Some(server_component)) = (
+                app_client_reference_ty,
+                app_client_reference.server_component(),
+            ) {
+                if let Some((client_chunks, _)) = client_references_chunks
+                    .client_component_client_chunks
+                    .get(&app_client_reference_ty)
+                {
+                    let server_component_name = server_component
+                        .server_path()
+                        .with_extension("".into())
+                        .to_string()
+                        .await?;
+
+                    let client_chunks_paths = client_chunks
+                        .await?
+                        .iter()
+                        .map(|chunk| chunk.ident().path())
+                        .try_join()
+                        .await?;
+
+                    let entry_css_files = entry_manifest
+                        .entry_css_files
+                        .entry(server_component_name.clone_value())
+                        .or_default();
+
+                    for chunk_path in client_chunks_paths {
+                        if chunk_path.extension_ref() == Some("css") {
+                            if let Some(path) = client_relative_path.get_path_to(&chunk_path) {
+                                entry_css_files.insert(path.into());
+                            }
+                        }
+                    }
+                }
+            }
+
             // An client component need to be emitted into the client reference manifest
             if let ClientReferenceType::EcmascriptClientReference(ecmascript_client_reference) =
                 app_client_reference_ty
@@ -221,6 +265,27 @@ impl ClientReferenceManifest {
             }
         }
 
+        // client chunk paths are rewritten once all of them are collected
+        if asset_path_transform.is_some() {
+            for entry in entry_manifest.client_modules.module_exports.values_mut() {
+                for chunk in entry.chunks.iter_mut() {
+                    *chunk = transform_asset_path(asset_path_transform, chunk.clone()).await?;
+                }
+            }
+            for files in entry_manifest
+                .entry_css_files
+                .values_mut()
+                .chain(entry_manifest.entry_js_files.values_mut())
+            {
+                let mut transformed_files = IndexSet::new();
+                for file in files.iter() {
+                    transformed_files
+                        .insert(transform_asset_path(asset_path_transform, file.clone()).await?);
+                }
+                *files = transformed_files;
+            }
+        }
+
         let client_reference_manifest_json = serde_json::to_string(&entry_manifest).unwrap();
 
         // We put normalized path for the each entry key and the manifest output path,
@@ -229,23 +294,28 @@ impl ClientReferenceManifest {
         // note this only applies to the manifests, assets are placed to the original
         // path still (same as webpack does)
         let normalized_manifest_entry = entry_name.replace("%5F", "_");
-        Ok(Vc::upcast(VirtualOutputAsset::new(
-            node_root.join(
-                format!("server/app{normalized_manifest_entry}_client-reference-manifest.js",)
+        let normalized_manifest_path_prefix = manifest_path_prefix.replace("%5F", "_");
+        Ok(Vc::upcast(
+            VirtualOutputAsset::new(
+                node_root.join(
+                    format!(
+                        "server/app{normalized_manifest_path_prefix}_client-reference-manifest.js",
+                    )
                     .into(),
-            ),
-            AssetContent::file(
-                File::from(formatdoc! {
-                    r#"
+                ),
+                AssetContent::file(
+                    File::from(formatdoc! {
+                        r#"
                         globalThis.__RSC_MANIFEST = globalThis.__RSC_MANIFEST || {{}};
                         globalThis.__RSC_MANIFEST[{entry_name}] = {manifest}
                     "#,
-                    entry_name = StringifyJs(&normalized_manifest_entry),
-                    manifest = &client_reference_manifest_json
-                })
-                .into(),
+                        entry_name = StringifyJs(&normalized_manifest_entry),
+                        manifest = &client_reference_manifest_json
+                    })
+                    .into(),
+                ),
             ),
-        )))
+        ))
     }
 }
 
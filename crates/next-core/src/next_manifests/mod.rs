@@ -2,13 +2,17 @@
 
 pub(crate) mod client_reference_manifest;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
+use anyhow::Result;
 use indexmap::{IndexMap, IndexSet};
 use serde::{Deserialize, Serialize};
-use turbo_tasks::{trace::TraceRawVcs, RcStr, TaskInput};
+use turbo_tasks::{trace::TraceRawVcs, RcStr, TaskInput, Vc};
 
-use crate::next_config::{CrossOriginConfig, Rewrites, RouteHas};
+use crate::{
+    app_segment_config::{NextRevalidate, NextSegmentFetchCache},
+    next_config::{CrossOriginConfig, HeaderValue, Rewrites, RouteHas},
+};
 
 #[derive(Serialize, Default, Debug)]
 pub struct PagesManifest {
@@ -26,12 +30,18 @@ pub struct BuildManifest {
     pub root_main_files: Vec<RcStr>,
     pub pages: HashMap<RcStr, Vec<RcStr>>,
     pub amp_first_pages: Vec<RcStr>,
+    /// Whether `pages` holds the entries of the app build manifest, which isn't
+    /// emitted separately then.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub unified: bool,
 }
 
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase", tag = "version")]
 #[allow(clippy::large_enum_variant)]
 pub enum MiddlewaresManifest {
+    #[serde(rename = "1")]
+    MiddlewaresManifestV1(MiddlewaresManifestV1),
     #[serde(rename = "2")]
     MiddlewaresManifestV2(MiddlewaresManifestV2),
     #[serde(other)]
@@ -62,7 +72,7 @@ pub struct MiddlewareMatcher {
     // When skipped next.js with fill that during merging.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub regexp: Option<RcStr>,
-    #[serde(skip_serializing_if = "bool_is_true")]
+    #[serde(default = "default_true", skip_serializing_if = "bool_is_true")]
     pub locale: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub has: Option<Vec<RouteHas>>,
@@ -87,7 +97,11 @@ fn bool_is_true(b: &bool) -> bool {
     *b
 }
 
-#[derive(Serialize, Default, Debug)]
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct EdgeFunctionDefinition {
     pub files: Vec<RcStr>,
     pub name: RcStr,
@@ -100,31 +114,31 @@ pub struct EdgeFunctionDefinition {
     pub env: IndexMap<RcStr, RcStr>,
 }
 
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct InstrumentationDefinition {
     pub files: Vec<RcStr>,
     pub name: RcStr,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub wasm: Vec<AssetBinding>,
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub assets: Vec<AssetBinding>,
 }
 
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetBinding {
     pub name: RcStr,
     pub file_path: RcStr,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(untagged)]
 pub enum Regions {
     Multiple(Vec<RcStr>),
     Single(RcStr),
 }
 
-#[derive(Serialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct MiddlewaresManifestV2 {
     pub sorted_middleware: Vec<RcStr>,
     pub middleware: HashMap<RcStr, EdgeFunctionDefinition>,
@@ -132,6 +146,94 @@ pub struct MiddlewaresManifestV2 {
     pub functions: HashMap<RcStr, EdgeFunctionDefinition>,
 }
 
+/// The legacy middleware manifest format, which only knows middleware with a
+/// single regexp each. Edge functions are listed as middleware, too.
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MiddlewaresManifestV1 {
+    pub sorted_middleware: Vec<RcStr>,
+    /// The location of each entry of `sorted_middleware`, and whether it is an
+    /// edge function rendering a page instead of a middleware.
+    pub client_info: Vec<(RcStr, bool)>,
+    pub middleware: BTreeMap<RcStr, EdgeFunctionDefinitionV1>,
+}
+
+#[derive(Serialize, Default, Debug, PartialEq, Eq)]
+pub struct EdgeFunctionDefinitionV1 {
+    /// The names of the environment variables available to the function.
+    pub env: Vec<RcStr>,
+    pub files: Vec<RcStr>,
+    pub name: RcStr,
+    pub page: RcStr,
+    pub regexp: RcStr,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub wasm: Vec<AssetBinding>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<AssetBinding>,
+}
+
+impl EdgeFunctionDefinitionV1 {
+    /// Converts `definition` to the legacy format. Its matchers are combined
+    /// into a single regexp matching any of them, where a matcher without a
+    /// regexp matches every path. `has`, `missing` and `locale` of the matchers
+    /// can't be expressed and are dropped.
+    pub fn from_v2(definition: &EdgeFunctionDefinition) -> Self {
+        let regexps = definition
+            .matchers
+            .iter()
+            .map(|matcher| matcher.regexp.as_deref().unwrap_or("^/.*$"))
+            .collect::<IndexSet<_>>();
+        let regexp = match regexps.len() {
+            0 => "^/.*$".into(),
+            1 => regexps[0].into(),
+            _ => regexps
+                .iter()
+                .map(|regexp| format!("(?:{regexp})"))
+                .collect::<Vec<_>>()
+                .join("|")
+                .into(),
+        };
+        Self {
+            env: definition.env.keys().cloned().collect(),
+            files: definition.files.clone(),
+            name: definition.name.clone(),
+            page: definition.page.clone(),
+            regexp,
+            wasm: definition.wasm.clone(),
+            assets: definition.assets.clone(),
+        }
+    }
+}
+
+impl MiddlewaresManifestV1 {
+    /// Converts `manifest` to the legacy format, which lists both the
+    /// middleware and the functions of `manifest` as middleware.
+    pub fn from_v2(manifest: &MiddlewaresManifestV2) -> Self {
+        let middleware = manifest
+            .middleware
+            .iter()
+            .chain(manifest.functions.iter())
+            .map(|(name, definition)| (name.clone(), EdgeFunctionDefinitionV1::from_v2(definition)))
+            .collect::<BTreeMap<_, _>>();
+        let mut sorted_middleware = manifest.sorted_middleware.clone();
+        sorted_middleware.extend(
+            middleware
+                .keys()
+                .filter(|name| !manifest.sorted_middleware.contains(name))
+                .cloned(),
+        );
+        let client_info = sorted_middleware
+            .iter()
+            .map(|name| (name.clone(), manifest.functions.contains_key(name)))
+            .collect();
+        Self {
+            sorted_middleware,
+            client_info,
+            middleware,
+        }
+    }
+}
+
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ReactLoadableManifest {
@@ -155,6 +257,28 @@ pub struct NextFontManifest {
     pub pages_using_size_adjust: bool,
 }
 
+/// The modules exposed as module federation remotes, keyed by the name they
+/// are exposed as.
+#[derive(Serialize, Default, Debug)]
+pub struct FederationManifest {
+    pub exposes: IndexMap<RcStr, FederationExposedModule>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct FederationExposedModule {
+    /// The path of the module, relative to the project directory.
+    pub module: RcStr,
+    /// The client chunks that load the module, relative to the client output
+    /// root.
+    pub chunks: Vec<RcStr>,
+}
+
+/// The CSS chunks of an app page to preload, in the order they are loaded.
+#[derive(Serialize, Default, Debug)]
+pub struct CssPreloadManifest {
+    pub files: Vec<RcStr>,
+}
+
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct AppPathsManifest {
@@ -162,6 +286,97 @@ pub struct AppPathsManifest {
     pub edge_server_app_paths: PagesManifest,
     #[serde(flatten)]
     pub node_server_app_paths: PagesManifest,
+    /// The suggested `Cache-Control` header of each route, keyed by page name.
+    /// See [crate::app_segment_config::NextSegmentConfig::cache_control_hint].
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub cache_control: HashMap<RcStr, RcStr>,
+    /// The `fetchCache` segment config of each route that sets it, including
+    /// through a parent layout, keyed by page name.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub fetch_cache: HashMap<RcStr, NextSegmentFetchCache>,
+    /// The route groups of each route that is in one, outermost first, keyed
+    /// by page name. See [crate::next_app::AppPage::route_groups].
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub route_groups: HashMap<RcStr, Vec<RcStr>>,
+    /// Whether each route handler that sets the `streaming` segment config
+    /// streams its responses, keyed by page name.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub streaming: HashMap<RcStr, bool>,
+}
+
+/// The headers to attach to the responses of app routes, keyed by page name.
+#[derive(Serialize, Default, Debug)]
+pub struct HeadersManifest<'a> {
+    #[serde(flatten)]
+    pub pages: HashMap<RcStr, &'a [HeaderValue]>,
+}
+
+/// The revalidation interval of app routes from their `revalidate` segment
+/// config, keyed by page name. Routes without a `revalidate` export in the
+/// route or any of its layouts are omitted.
+#[derive(Serialize, Default, Debug)]
+pub struct AppRevalidateManifest {
+    #[serde(flatten)]
+    pub pages: HashMap<RcStr, Revalidate>,
+}
+
+/// A `revalidate` value as it is written to manifests: `false` if the route is
+/// cached indefinitely, otherwise the interval in seconds.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Revalidate {
+    Never(bool),
+    Seconds(u32),
+}
+
+impl From<NextRevalidate> for Revalidate {
+    fn from(revalidate: NextRevalidate) -> Self {
+        match revalidate {
+            // `force-cache` caches the route indefinitely, like `false`.
+            NextRevalidate::Never | NextRevalidate::ForceCache => Revalidate::Never(false),
+            NextRevalidate::Frequency { seconds } => Revalidate::Seconds(seconds),
+        }
+    }
+}
+
+/// The prerender config of all app routes that can be prerendered, in the
+/// format of the `prerender-manifest.json` of the pages router.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerenderManifest {
+    pub version: u32,
+    pub routes: IndexMap<RcStr, PrerenderRoute>,
+    pub dynamic_routes: IndexMap<RcStr, DynamicPrerenderRoute>,
+    pub not_found_routes: Vec<RcStr>,
+}
+
+impl Default for PrerenderManifest {
+    fn default() -> Self {
+        Self {
+            version: 4,
+            routes: Default::default(),
+            dynamic_routes: Default::default(),
+            not_found_routes: Default::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PrerenderRoute {
+    pub initial_revalidate_seconds: Revalidate,
+    pub src_route: RcStr,
+    pub data_route: RcStr,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicPrerenderRoute {
+    pub route_regex: RcStr,
+    pub data_route: RcStr,
+    /// `false` if only params returned by `generateStaticParams` are served,
+    /// `null` if other params are rendered on demand.
+    pub fallback: Option<bool>,
 }
 
 // A struct represent a single entry in react-loadable-manifest.json.
@@ -175,6 +390,18 @@ pub struct LoadableManifest {
     pub files: Vec<RcStr>,
 }
 
+/// The streaming format of the loadable manifest. Dynamic imports are grouped
+/// by the module importing them, so a runtime can look up the imports of the
+/// modules it renders without knowing the full import ids.
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamingLoadableManifest {
+    pub version: u32,
+    /// A map from importing module to a map from import request to the files
+    /// it loads.
+    pub modules: HashMap<RcStr, HashMap<RcStr, Vec<RcStr>>>,
+}
+
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ServerReferenceManifest<'a> {
@@ -308,3 +535,216 @@ pub struct ClientBuildManifest<'a> {
     #[serde(flatten)]
     pub pages: HashMap<RcStr, Vec<&'a str>>,
 }
+
+/// The app routes loading each chunk, keyed by the path of the chunk. Client
+/// chunks are relative to the client root, server chunks to the node root.
+#[derive(Serialize, Default, Debug)]
+pub struct ChunkRoutesIndex {
+    pub chunks: BTreeMap<RcStr, Vec<RcStr>>,
+}
+
+/// The client chunks of the error boundaries of an app page, so their UI can
+/// render without loading chunks first.
+#[derive(Serialize, Default, Debug)]
+pub struct ErrorPreloadManifest {
+    pub files: Vec<RcStr>,
+}
+
+/// The app routes that can be prerendered at build time and served as static
+/// files. See `AppProject::static_routes_manifest`.
+#[derive(Serialize, Default, Debug)]
+pub struct StaticRoutesManifest {
+    /// The pathnames of the routes.
+    pub routes: BTreeSet<RcStr>,
+}
+
+/// The source files of all modules that a route is built from, including
+/// those in `node_modules`.
+#[derive(Serialize, Default, Debug)]
+pub struct SourcesManifest {
+    /// The paths of the source files, relative to the project root.
+    pub files: BTreeSet<RcStr>,
+}
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewManifest {
+    /// Mapping of client asset path to its signed URL.
+    pub assets: IndexMap<RcStr, RcStr>,
+}
+
+/// The `NEXT_PUBLIC_` environment variables inlined into the client bundle of
+/// a single route.
+#[derive(Serialize, Default, Debug)]
+pub struct EnvManifest {
+    pub variables: Vec<RcStr>,
+}
+
+/// Bundle analysis of the chunks of a single route, similar to the
+/// `stats.json` of webpack.
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteStats {
+    pub client_chunks: Vec<ChunkStats>,
+    pub server_chunks: Vec<ChunkStats>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ChunkStats {
+    /// Relative to the client or node root.
+    pub path: RcStr,
+    /// The size of the emitted chunk in bytes.
+    pub size: usize,
+    /// The largest modules of the chunk, in descending order of size. Empty
+    /// for non-ecmascript chunks.
+    pub modules: Vec<ModuleStats>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ModuleStats {
+    pub name: RcStr,
+    /// The size of the generated code of the module in bytes.
+    pub size: usize,
+}
+
+/// An OpenAPI document describing the methods of a single route handler.
+#[derive(Serialize, Debug)]
+pub struct OpenApiStub {
+    pub openapi: &'static str,
+    pub paths: IndexMap<RcStr, IndexMap<RcStr, OpenApiOperation>>,
+}
+
+#[derive(Serialize, Default, Debug)]
+pub struct OpenApiOperation {
+    pub parameters: Vec<OpenApiParameter>,
+    pub responses: IndexMap<RcStr, OpenApiResponse>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct OpenApiParameter {
+    pub name: RcStr,
+    #[serde(rename = "in")]
+    pub location: &'static str,
+    pub required: bool,
+}
+
+#[derive(Serialize, Default, Debug)]
+pub struct OpenApiResponse {
+    pub description: RcStr,
+}
+
+/// The server actions of all app routes, keyed by action id.
+#[derive(Serialize, Default, Debug)]
+pub struct ServerActionsRoutesManifest {
+    pub actions: IndexMap<RcStr, ServerActionRoutes>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ServerActionRoutes {
+    /// The HTTP method server actions are invoked with, which is always
+    /// `POST`.
+    pub method: RcStr,
+    /// The original names of the app routes that can invoke the action.
+    pub routes: Vec<RcStr>,
+}
+
+#[derive(Serialize, Default, Debug)]
+pub struct WasmInventoryManifest {
+    /// All WASM assets of the app routes, with their binding names.
+    pub wasm: Vec<AssetBinding>,
+}
+
+/// Rewrites the client asset paths written into manifests, e.g. to match the
+/// paths a CDN serves them from.
+#[turbo_tasks::value_trait]
+pub trait AssetPathTransform {
+    /// Returns the path to write into manifests for the client asset at
+    /// `path`, which is relative to the client output root.
+    fn transform(self: Vc<Self>, path: RcStr) -> Vc<RcStr>;
+}
+
+/// Applies `transform` to `path`. Without a transform, `path` is returned
+/// unchanged.
+pub async fn transform_asset_path(
+    transform: Option<Vc<Box<dyn AssetPathTransform>>>,
+    path: RcStr,
+) -> Result<RcStr> {
+    Ok(match transform {
+        Some(transform) => transform.transform(path).await?.clone_value(),
+        None => path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        EdgeFunctionDefinition, EdgeFunctionDefinitionV1, MiddlewareMatcher, MiddlewaresManifestV1,
+        MiddlewaresManifestV2,
+    };
+
+    fn definition(name: &str, regexps: &[&str]) -> EdgeFunctionDefinition {
+        EdgeFunctionDefinition {
+            files: vec![format!("server/{name}.js").into()],
+            name: name.into(),
+            page: format!("/{name}").into(),
+            matchers: regexps
+                .iter()
+                .map(|regexp| MiddlewareMatcher {
+                    regexp: Some((*regexp).into()),
+                    ..Default::default()
+                })
+                .collect(),
+            env: [("KEY".into(), "value".into())].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_single_matcher() {
+        let definition = EdgeFunctionDefinitionV1::from_v2(&definition("a", &["^/a$"]));
+        assert_eq!(definition.regexp, "^/a$");
+        assert_eq!(definition.env, vec!["KEY"]);
+        assert_eq!(definition.files, vec!["server/a.js"]);
+        assert_eq!(definition.page, "/a");
+    }
+
+    #[test]
+    fn test_combined_matchers() {
+        let definition =
+            EdgeFunctionDefinitionV1::from_v2(&definition("a", &["^/a$", "^/b$", "^/a$"]));
+        assert_eq!(definition.regexp, "(?:^/a$)|(?:^/b$)");
+    }
+
+    #[test]
+    fn test_missing_regexp() {
+        let mut definition = definition("a", &[]);
+        let v1 = EdgeFunctionDefinitionV1::from_v2(&definition);
+        assert_eq!(v1.regexp, "^/.*$");
+
+        definition.matchers.push(MiddlewareMatcher::default());
+        let v1 = EdgeFunctionDefinitionV1::from_v2(&definition);
+        assert_eq!(v1.regexp, "^/.*$");
+    }
+
+    #[test]
+    fn test_manifest() {
+        let manifest = MiddlewaresManifestV2 {
+            sorted_middleware: vec!["/".into()],
+            middleware: [("/".into(), definition("middleware", &["^/.*$"]))]
+                .into_iter()
+                .collect(),
+            functions: [("/page".into(), definition("page", &["^/page$"]))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let manifest = MiddlewaresManifestV1::from_v2(&manifest);
+        assert_eq!(manifest.sorted_middleware, vec!["/", "/page"]);
+        assert_eq!(
+            manifest.client_info,
+            vec![("/".into(), false), ("/page".into(), true)]
+        );
+        assert_eq!(manifest.middleware.len(), 2);
+        assert_eq!(manifest.middleware["/page"].regexp, "^/page$");
+    }
+}
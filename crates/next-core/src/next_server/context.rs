@@ -14,7 +14,7 @@ use turbopack::{
     transition::Transition,
 };
 use turbopack_core::{
-    chunk::module_id_strategies::ModuleIdStrategy,
+    chunk::{module_id_strategies::ModuleIdStrategy, ChunkSizeThresholds},
     compile_time_info::{
         CompileTimeDefineValue, CompileTimeDefines, CompileTimeInfo, DefineableNameSegment,
         FreeVarReferences,
@@ -926,9 +926,11 @@ pub async fn get_server_chunking_context_with_client_assets(
     project_path: Vc<FileSystemPath>,
     node_root: Vc<FileSystemPath>,
     client_root: Vc<FileSystemPath>,
+    client_assets_path: Vc<FileSystemPath>,
     asset_prefix: Vc<Option<RcStr>>,
     environment: Vc<Environment>,
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    chunk_size_thresholds: Vc<ChunkSizeThresholds>,
 ) -> Result<Vc<NodeJsChunkingContext>> {
     let next_mode = mode.await?;
     // TODO(alexkirsz) This should return a trait that can be implemented by the
@@ -939,13 +941,14 @@ pub async fn get_server_chunking_context_with_client_assets(
         node_root,
         client_root,
         node_root.join("server/chunks/ssr".into()),
-        client_root.join("static/media".into()),
+        client_assets_path,
         environment,
         next_mode.runtime_type(),
     )
     .asset_prefix(asset_prefix)
     .minify_type(next_mode.minify_type())
     .module_id_strategy(module_id_strategy)
+    .chunk_size_thresholds(*chunk_size_thresholds.await?)
     .build())
 }
 
@@ -956,6 +959,7 @@ pub async fn get_server_chunking_context(
     node_root: Vc<FileSystemPath>,
     environment: Vc<Environment>,
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    chunk_size_thresholds: Vc<ChunkSizeThresholds>,
 ) -> Result<Vc<NodeJsChunkingContext>> {
     let next_mode = mode.await?;
     // TODO(alexkirsz) This should return a trait that can be implemented by the
@@ -972,5 +976,6 @@ pub async fn get_server_chunking_context(
     )
     .minify_type(next_mode.minify_type())
     .module_id_strategy(module_id_strategy)
+    .chunk_size_thresholds(*chunk_size_thresholds.await?)
     .build())
 }
@@ -1519,6 +1519,7 @@ export default async function build(
           productionRewrites: customRoutes.rewrites,
           entrypoints: currentEntrypoints,
         })
+        await project.writeCompressedManifests()
 
         const errors: {
           page: string
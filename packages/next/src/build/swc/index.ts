@@ -684,6 +684,12 @@ export interface Project {
     aggregationMs: number
   ): AsyncIterableIterator<TurbopackResult<UpdateMessage>>
 
+  /**
+   * Writes `server/compressed-manifests.json` when `compressManifests` is
+   * enabled, telling the runtime which manifests are gzip-compressed.
+   */
+  writeCompressedManifests(): Promise<void>
+
   shutdown(): Promise<void>
 
   onExit(): Promise<void>
@@ -720,6 +726,13 @@ export interface Endpoint {
   /** Write files for the endpoint to disk. */
   writeToDisk(): Promise<TurbopackResult<WrittenEndpoint>>
 
+  /**
+   * Force the next writeToDisk() to rebuild the endpoint's output instead of
+   * returning the cached result, e.g. after a change the file watcher can't
+   * see. Files are not read from disk again.
+   */
+  invalidate(): Promise<void>
+
   /**
    * Listen to client-side changes to the endpoint.
    * After clientChanged() has been awaited it will listen to changes.
@@ -783,6 +796,13 @@ export type WrittenEndpoint =
       serverPaths: []
       config: EndpointConfig
     }
+  | {
+      /** The build was cancelled before it wrote anything. */
+      type: 'cancelled'
+      clientPaths: []
+      serverPaths: []
+      config: EndpointConfig
+    }
 
 function rustifyEnv(env: Record<string, string>): RustifiedEnv {
   return Object.entries(env)
@@ -1108,6 +1128,10 @@ function bindingToApi(
       return subscription
     }
 
+    writeCompressedManifests(): Promise<void> {
+      return binding.projectWriteCompressedManifests(this._nativeProject)
+    }
+
     shutdown(): Promise<void> {
       return binding.projectShutdown(this._nativeProject)
     }
@@ -1130,6 +1154,12 @@ function bindingToApi(
       )
     }
 
+    async invalidate(): Promise<void> {
+      await withErrorCause(() =>
+        binding.endpointInvalidate(this._nativeEndpoint)
+      )
+    }
+
     async clientChanged(): Promise<AsyncIterableIterator<TurbopackResult<{}>>> {
       const clientSubscription = subscribe<TurbopackResult>(
         false,
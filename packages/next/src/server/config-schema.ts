@@ -392,6 +392,62 @@ export const configSchema: zod.ZodType<NextConfig> = z.lazy(() =>
             treeShaking: z.boolean().optional(),
             memoryLimit: z.number().optional(),
             moduleIdStrategy: z.enum(['named', 'deterministic']).optional(),
+            clientReferenceCycles: z.enum(['error', 'fail']).optional(),
+            splitServerEntry: z.boolean().optional(),
+            sharedServerBase: z.boolean().optional(),
+            routeOpenApiStubs: z.boolean().optional(),
+            dynamicImportsManifest: z.boolean().optional(),
+            sanitizeRoutePaths: z.boolean().optional(),
+            contentAddressedOutput: z.boolean().optional(),
+            endpointMemoryReport: z.boolean().optional(),
+            disablePolyfill: z.boolean().optional(),
+            rscOnly: z.boolean().optional(),
+            validateEdgeNodeBuiltins: z.boolean().optional(),
+            loadableManifestFormat: z.enum(['classic', 'streaming']).optional(),
+            minChunkSize: z.number().optional(),
+            maxChunkSize: z.number().optional(),
+            routeHeaders: z
+              .record(
+                z.string(),
+                z.array(z.object({ key: z.string(), value: z.string() }))
+              )
+              .optional(),
+            serverActionsManifestName: z.string().optional(),
+            reportDuplicateClientModules: z.boolean().optional(),
+            compressManifests: z.boolean().optional(),
+            analyze: z.boolean().optional(),
+            sortRootMainFiles: z.boolean().optional(),
+            envManifest: z.boolean().optional(),
+            strictRuntimeResolution: z.boolean().optional(),
+            unsplitRouteHandlers: z.boolean().optional(),
+            federationExposes: z.record(z.string(), z.string()).optional(),
+            failingIssueSeverity: z
+              .enum([
+                'bug',
+                'fatal',
+                'error',
+                'warning',
+                'hint',
+                'note',
+                'suggestion',
+                'info',
+              ])
+              .optional(),
+            debugIds: z.boolean().optional(),
+            compactManifests: z.boolean().optional(),
+            serverEntryModules: z.array(z.string()).optional(),
+            edgeChunkLoading: z.boolean().optional(),
+            appPathsManifestKey: z
+              .enum(['originalName', 'pathname', 'both'])
+              .optional(),
+            edgeFunctionSizeLimit: z.number().int().optional(),
+            flatClientAssets: z.boolean().optional(),
+            unifiedBuildManifest: z.boolean().optional(),
+            legacyMiddlewareManifest: z.boolean().optional(),
+            sourcesManifest: z.boolean().optional(),
+            metadataConflictPolicy: z
+              .enum(['error', 'firstWins', 'lastWins'])
+              .optional(),
           })
           .optional(),
         optimizePackageImports: z.array(z.string()).optional(),
@@ -166,6 +166,264 @@ export interface ExperimentalTurboOptions {
    */
   moduleIdStrategy?: 'named' | 'deterministic'
 
+  /**
+   * Reports cycles between client references in app pages. `'fail'` also
+   * fails the build when a cycle is found.
+   */
+  clientReferenceCycles?: 'error' | 'fail'
+
+  /**
+   * Moves the dependencies of app server entries into separate chunks instead
+   * of bundling them into a single entry chunk.
+   */
+  splitServerEntry?: boolean
+
+  /**
+   * Seeds the chunk groups of app endpoints from chunks shared by all
+   * endpoints, so common modules aren't duplicated across endpoints.
+   */
+  sharedServerBase?: boolean
+
+  /**
+   * Emits an OpenAPI stub (`openapi.json`) listing the exported HTTP methods
+   * of every app route handler.
+   */
+  routeOpenApiStubs?: boolean
+
+  /**
+   * Emit a `dynamic-imports-manifest.json` per app route, listing the chunks of
+   * each dynamic import for preloading.
+   */
+  dynamicImportsManifest?: boolean
+
+  /**
+   * Percent-encode characters in app route names that are not allowed in file
+   * names on all platforms (e.g. `:` and `*`) when building output paths.
+   */
+  sanitizeRoutePaths?: boolean
+
+  /**
+   * Store emitted files at `content/<hash>` in the output directory. Their
+   * regular path is a symlink to the content-addressed path, and
+   * `path-map.json` maps the regular paths to the content-addressed paths.
+   */
+  contentAddressedOutput?: boolean
+
+  /**
+   * Log the approximate memory usage of building each app route, split by the
+   * phase of the build. This is only meant for debugging.
+   */
+  endpointMemoryReport?: boolean
+
+  /**
+   * Don't emit `polyfill-nomodule.js` for app routes. Only use this when all
+   * targeted browsers support ES modules.
+   */
+  disablePolyfill?: boolean
+
+  /**
+   * Only build the RSC payload of app pages. The HTML endpoint of a page then
+   * builds the same output as its RSC endpoint and no HTML is server-side
+   * rendered.
+   */
+  rscOnly?: boolean
+
+  /**
+   * Report an error for every Node.js built-in module imported by an app route
+   * using the Edge Runtime. Set to `false` to suppress these errors.
+   *
+   * @default true
+   */
+  validateEdgeNodeBuiltins?: boolean
+
+  /**
+   * The format of the react-loadable manifest. `'streaming'` groups dynamic
+   * imports by the importing module and requires a runtime that supports it.
+   *
+   * @default 'classic'
+   */
+  loadableManifestFormat?: 'classic' | 'streaming'
+
+  /**
+   * The total size of modules in bytes below which chunks are merged with other
+   * small chunks.
+   *
+   * @default 100000
+   */
+  minChunkSize?: number
+
+  /**
+   * The total size of modules in bytes above which chunks are split further.
+   *
+   * @default 1000000
+   */
+  maxChunkSize?: number
+
+  /**
+   * Headers to attach to the responses of app routes, keyed by the page name of
+   * the route, e.g. `/blog/[slug]/page`. They are emitted into a
+   * `headers-manifest.json` next to the route's other manifests.
+   */
+  routeHeaders?: Record<string, Array<{ key: string; value: string }>>
+
+  /**
+   * The file name of the server actions manifest, without extension. The
+   * runtime must be configured to load the manifest under the same name.
+   *
+   * @default 'server-reference-manifest'
+   */
+  serverActionsManifestName?: string
+
+  /**
+   * Reports modules that are bundled into more than one client chunk of an app
+   * page, to help tune code splitting.
+   */
+  reportDuplicateClientModules?: boolean
+
+  /**
+   * Emits the server manifests of app routes, like `app-paths-manifest.json`
+   * and `middleware-manifest.json`, gzip-compressed as `.json.gz` files.
+   * `server/compressed-manifests.json` lists the compressed manifests.
+   */
+  compressManifests?: boolean
+
+  /**
+   * Emits a `stats.json` for each app route, listing the sizes of its client
+   * and server chunks and the modules contributing most to them.
+   */
+  analyze?: boolean
+
+  /**
+   * Sorts the `rootMainFiles` of the app build manifest by path, so their order
+   * is deterministic. The chunk evaluating the entries stays last.
+   */
+  sortRootMainFiles?: boolean
+
+  /**
+   * Emits an `env-manifest.json` for each app page, listing the `NEXT_PUBLIC_`
+   * environment variables inlined into its client bundle.
+   */
+  envManifest?: boolean
+
+  /**
+   * Fails the build when a critical runtime module, like the client main
+   * module, can't be resolved, instead of reporting an error.
+   */
+  strictRuntimeResolution?: boolean
+
+  /**
+   * Chunks each route handler running on Node.js without splitting its chunks
+   * by size and without sharing chunks with other routes, so it can be
+   * deployed as an isolated function, at the cost of larger bundles. The
+   * route's modules end up in one chunk per chunk type, which the entry chunk
+   * loads through the runtime. Dynamic imports still get their own chunks.
+   */
+  unsplitRouteHandlers?: boolean
+
+  /**
+   * Modules to expose as module federation remotes, keyed by the name they are
+   * exposed as, e.g. `{ './Button': './components/Button.tsx' }`. Paths are
+   * relative to the project directory. The exposed modules and their chunks
+   * are listed in `federation-manifest.json`.
+   */
+  federationExposes?: Record<string, string>
+
+  /**
+   * Fails writing a route's output when building it reported an issue of this
+   * severity or a more severe one, e.g. `'warning'` to fail on warnings. The
+   * files of the route are still written.
+   */
+  failingIssueSeverity?:
+    | 'bug'
+    | 'fatal'
+    | 'error'
+    | 'warning'
+    | 'hint'
+    | 'note'
+    | 'suggestion'
+    | 'info'
+
+  /**
+   * Add a `//# debugId=` comment to each JavaScript chunk of the app directory
+   * and the same `debugId` to its source map, so that error trackers can match
+   * them without relying on file names. The ID is derived from the content of
+   * the chunk.
+   */
+  debugIds?: boolean
+
+  /**
+   * Write the manifests of app routes as compact instead of pretty-printed
+   * JSON. Defaults to `true` for `next build` and `false` for `next dev`.
+   */
+  compactManifests?: boolean
+
+  /**
+   * Modules that are evaluated by the server entry chunk of every app route
+   * using the Node.js runtime, instead of being loaded lazily. The specifiers
+   * are resolved from the project directory.
+   */
+  serverEntryModules?: string[]
+
+  /**
+   * For edge platforms that support dynamic imports: keep the SSR chunks of
+   * client components out of the files of edge functions, so that they are
+   * loaded when needed instead of during the initialization of the function.
+   */
+  edgeChunkLoading?: boolean
+
+  /**
+   * Which names the entries of `app-paths-manifest.json` are keyed by.
+   * `'originalName'` (the default) uses the name of the route in the app
+   * directory, including route groups, parallel route slots, interception
+   * markers and the `/page` or `/route` suffix, e.g.
+   * `/(shop)/@modal/(.)cart/page`. `'pathname'` uses the URL path the route
+   * matches, e.g. `/cart`. `'both'` adds entries for both names.
+   */
+  appPathsManifestKey?: 'originalName' | 'pathname' | 'both'
+
+  /**
+   * The total size in bytes of the files of an edge function above which an
+   * error is reported for it, e.g. the bundle size limit of the platform
+   * deploying it. Combine with `failingIssueSeverity` to fail the build.
+   */
+  edgeFunctionSizeLimit?: number
+
+  /**
+   * Emit client assets, e.g. images, into `static/chunks` next to the client
+   * chunks instead of `static/media`, so all client files are in a single
+   * flat directory. Their filenames are hashed, so they can't collide.
+   */
+  flatClientAssets?: boolean
+
+  /**
+   * Merge the `pages` of the `app-build-manifest.json` of each app page into
+   * its `build-manifest.json`, which then has `unified: true`, instead of
+   * emitting both files.
+   */
+  unifiedBuildManifest?: boolean
+
+  /**
+   * Additionally emit the middleware manifest of each app route using the
+   * edge runtime in the legacy version 1 format, as
+   * `server/app/<route>/legacy-middleware-manifest.json`.
+   */
+  legacyMiddlewareManifest?: boolean
+
+  /**
+   * Emit `server/app/<route>/sources-manifest.json`, which lists the source
+   * files, including those in `node_modules`, of all modules each app route is
+   * built from, e.g. for license scanning. This slows down builds.
+   */
+  sourcesManifest?: boolean
+
+  /**
+   * What happens when several metadata files, e.g. `opengraph-image.png` and
+   * `opengraph-image.tsx` in the same directory, resolve to the same route:
+   * report an error, or report a warning and use the first or last file in
+   * directory order. Defaults to `'error'`.
+   */
+  metadataConflictPolicy?: 'error' | 'firstWins' | 'lastWins'
+
   /**
    * This is the repo root usually and only files above this
    * directory can be resolved by turbopack.
@@ -41,6 +41,7 @@ import { getEntryKey, type EntryKey } from './entry-key'
 import type { CustomRoutes } from '../../../lib/load-custom-routes'
 import { getSortedRoutes } from '../../../shared/lib/router/utils'
 import { existsSync } from 'fs'
+import { gunzipSync } from 'zlib'
 import { addMetadataIdToRoute, addRouteSuffix, removeRouteSuffix } from '../turbopack-utils'
 import { tryToParsePath } from '../../../lib/try-to-parse-path'
 
@@ -81,7 +82,7 @@ async function readPartialManifest<T>(
     name
   )
   // existsSync is faster than using the async version
-  if(!existsSync(manifestPath) && page.endsWith('/route')) {
+  if(!existsSync(manifestPath) && !existsSync(`${manifestPath}.gz`) && page.endsWith('/route')) {
     // TODO: Improve implementation of metadata routes, currently it requires this extra check for the variants of the files that can be written.
     const metadataPage = addRouteSuffix(addMetadataIdToRoute(removeRouteSuffix(page.replace(/\/sitemap\.xml\/route$/, '/sitemap/route'))))
     manifestPath = posix.join(
@@ -96,6 +97,12 @@ async function readPartialManifest<T>(
       name
     )
   }
+  // With `compressManifests`, manifests are only emitted gzip-compressed.
+  if (!existsSync(manifestPath) && existsSync(`${manifestPath}.gz`)) {
+    return JSON.parse(
+      gunzipSync(await readFile(`${manifestPath}.gz`)).toString('utf-8')
+    ) as T
+  }
   return JSON.parse(await readFile(posix.join(manifestPath), 'utf-8')) as T
 }
 
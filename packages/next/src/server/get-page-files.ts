@@ -16,6 +16,9 @@ export type BuildManifest = {
     [page: string]: readonly string[]
   }
   ampFirstPages: readonly string[]
+  // set when `pages` holds the entries of the app build manifest, which isn't
+  // emitted separately then (`experimental.turbo.unifiedBuildManifest`)
+  unified?: boolean
 }
 
 export function getPageFiles(
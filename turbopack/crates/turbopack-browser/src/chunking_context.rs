@@ -7,7 +7,7 @@ use turbopack_core::{
         availability_info::AvailabilityInfo,
         chunk_group::{make_chunk_group, MakeChunkGroupResult},
         module_id_strategies::{DevModuleIdStrategy, ModuleIdStrategy},
-        Chunk, ChunkGroupResult, ChunkItem, ChunkableModule, ChunkingContext,
+        Chunk, ChunkGroupResult, ChunkItem, ChunkSizeThresholds, ChunkableModule, ChunkingContext,
         EntryChunkGroupResult, EvaluatableAssets, MinifyType, ModuleId,
     },
     environment::Environment,
@@ -83,6 +83,11 @@ impl BrowserChunkingContextBuilder {
         self
     }
 
+    pub fn chunk_size_thresholds(mut self, chunk_size_thresholds: ChunkSizeThresholds) -> Self {
+        self.chunking_context.chunk_size_thresholds = chunk_size_thresholds;
+        self
+    }
+
     pub fn build(self) -> Vc<BrowserChunkingContext> {
         BrowserChunkingContext::new(Value::new(self.chunking_context))
     }
@@ -130,6 +135,8 @@ pub struct BrowserChunkingContext {
     manifest_chunks: bool,
     /// The module id strategy to use
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    /// The chunk item sizes at which chunks are split or merged
+    chunk_size_thresholds: ChunkSizeThresholds,
 }
 
 impl BrowserChunkingContext {
@@ -160,6 +167,7 @@ impl BrowserChunkingContext {
                 minify_type: MinifyType::NoMinify,
                 manifest_chunks: false,
                 module_id_strategy: Vc::upcast(DevModuleIdStrategy::new()),
+                chunk_size_thresholds: Default::default(),
             },
         }
     }
@@ -349,6 +357,11 @@ impl ChunkingContext for BrowserChunkingContext {
         Vc::cell(self.enable_hot_module_replacement)
     }
 
+    #[turbo_tasks::function]
+    fn chunk_size_thresholds(&self) -> Vc<ChunkSizeThresholds> {
+        self.chunk_size_thresholds.cell()
+    }
+
     #[turbo_tasks::function]
     async fn chunk_group(
         self: Vc<Self>,
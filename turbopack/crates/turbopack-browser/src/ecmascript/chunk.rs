@@ -16,7 +16,7 @@ use crate::{ecmascript::content::EcmascriptDevChunkContent, BrowserChunkingConte
 
 /// Development Ecmascript chunk.
 #[turbo_tasks::value(shared)]
-pub(crate) struct EcmascriptDevChunk {
+pub struct EcmascriptDevChunk {
     chunking_context: Vc<BrowserChunkingContext>,
     chunk: Vc<EcmascriptChunk>,
 }
@@ -35,6 +35,12 @@ impl EcmascriptDevChunk {
         }
         .cell()
     }
+
+    /// The chunk this output asset is generated from.
+    #[turbo_tasks::function]
+    pub fn chunk(&self) -> Vc<EcmascriptChunk> {
+        self.chunk
+    }
 }
 
 #[turbo_tasks::value_impl]
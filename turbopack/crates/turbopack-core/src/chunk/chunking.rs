@@ -11,8 +11,8 @@ use tracing::Level;
 use turbo_tasks::{RcStr, ReadRef, TryJoinIterExt, ValueToString, Vc};
 
 use super::{
-    AsyncModuleInfo, Chunk, ChunkItem, ChunkItemsWithAsyncModuleInfo, ChunkType, ChunkingContext,
-    Chunks,
+    AsyncModuleInfo, Chunk, ChunkItem, ChunkItemsWithAsyncModuleInfo, ChunkSizeThresholds,
+    ChunkType, ChunkingContext, Chunks,
 };
 use crate::output::OutputAssets;
 
@@ -65,6 +65,8 @@ pub async fn make_chunks(
             .push((chunk_item, async_info, chunk_item_info));
     }
 
+    let thresholds = *chunking_context.chunk_size_thresholds().await?;
+
     let mut chunks = Vec::new();
     for (ty, chunk_items) in map {
         let ty_name = ty.to_string().await?;
@@ -85,6 +87,7 @@ pub async fn make_chunks(
         let mut split_context = SplitContext {
             ty,
             chunking_context,
+            thresholds,
             chunks: &mut chunks,
             referenced_output_assets: &mut referenced_output_assets,
             empty_referenced_output_assets: OutputAssets::empty().resolve().await?,
@@ -120,6 +123,7 @@ type ChunkItemWithInfo = (
 struct SplitContext<'a> {
     ty: Vc<Box<dyn ChunkType>>,
     chunking_context: Vc<Box<dyn ChunkingContext>>,
+    thresholds: ChunkSizeThresholds,
     chunks: &'a mut Vec<Vc<Box<dyn Chunk>>>,
     referenced_output_assets: &'a mut Vc<OutputAssets>,
     empty_referenced_output_assets: Vc<OutputAssets>,
@@ -135,7 +139,8 @@ async fn handle_split_group(
     split_context: &mut SplitContext<'_>,
     remaining: Option<&mut Vec<ChunkItemWithInfo>>,
 ) -> Result<bool> {
-    Ok(match (chunk_size(chunk_items), remaining) {
+    let size = chunk_size(chunk_items, split_context.thresholds);
+    Ok(match (size, remaining) {
         (ChunkSize::Large, _) => false,
         (ChunkSize::Perfect, _) | (ChunkSize::Small, None) => {
             make_chunk(take(chunk_items), key, split_context).await?;
@@ -343,9 +348,6 @@ fn folder_name(ident: &str, location: usize) -> (&str, Option<usize>) {
     }
 }
 
-const LARGE_CHUNK: usize = 1_000_000;
-const SMALL_CHUNK: usize = 100_000;
-
 enum ChunkSize {
     Large,
     Perfect,
@@ -354,16 +356,54 @@ enum ChunkSize {
 
 /// Determines the total size of the passed chunk items. Returns too small, too
 /// large or perfect fit.
-fn chunk_size(chunk_items: &[ChunkItemWithInfo]) -> ChunkSize {
-    let mut total_size = 0;
-    for (_, _, size, _) in chunk_items {
-        total_size += size;
-    }
-    if total_size >= LARGE_CHUNK {
+fn chunk_size(chunk_items: &[ChunkItemWithInfo], thresholds: ChunkSizeThresholds) -> ChunkSize {
+    let total_size = chunk_items
+        .iter()
+        .fold(0, |total_size: usize, (_, _, size, _)| {
+            total_size.saturating_add(*size)
+        });
+    total_chunk_size(total_size, thresholds)
+}
+
+/// Determines whether `total_size` is too small, too large or a perfect fit.
+fn total_chunk_size(total_size: usize, thresholds: ChunkSizeThresholds) -> ChunkSize {
+    if total_size >= thresholds.max {
         ChunkSize::Large
-    } else if total_size > SMALL_CHUNK {
+    } else if total_size > thresholds.min {
         ChunkSize::Perfect
     } else {
         ChunkSize::Small
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsplit_thresholds_never_split_chunk_items() {
+        // Chunk items are only split off when a chunk is too large or a
+        // perfect fit, so with unsplit thresholds each chunk type of a chunk
+        // group results in exactly one chunk.
+        for total_size in [0, 100_000, 1_000_000, usize::MAX - 1] {
+            assert!(matches!(
+                total_chunk_size(total_size, ChunkSizeThresholds::unsplit()),
+                ChunkSize::Small
+            ));
+        }
+    }
+
+    #[test]
+    fn default_thresholds_split_large_chunk_items() {
+        let thresholds = ChunkSizeThresholds::default();
+        assert!(matches!(total_chunk_size(0, thresholds), ChunkSize::Small));
+        assert!(matches!(
+            total_chunk_size(500_000, thresholds),
+            ChunkSize::Perfect
+        ));
+        assert!(matches!(
+            total_chunk_size(1_000_000, thresholds),
+            ChunkSize::Large
+        ));
+    }
+}
@@ -35,6 +35,37 @@ pub enum MinifyType {
     NoMinify,
 }
 
+/// The total chunk item sizes that decide how chunk items are split into
+/// chunks. Groups of chunk items of at least `max` bytes are split further,
+/// groups of at most `min` bytes are merged with other small groups if
+/// possible.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Copy, Hash)]
+pub struct ChunkSizeThresholds {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl ChunkSizeThresholds {
+    /// Thresholds that never split chunk items by size, so all chunk items of
+    /// a chunk type in a chunk group end up in a single chunk.
+    pub fn unsplit() -> Self {
+        ChunkSizeThresholds {
+            min: usize::MAX,
+            max: usize::MAX,
+        }
+    }
+}
+
+impl Default for ChunkSizeThresholds {
+    fn default() -> Self {
+        ChunkSizeThresholds {
+            min: 100_000,
+            max: 1_000_000,
+        }
+    }
+}
+
 #[turbo_tasks::value(shared)]
 pub struct ChunkGroupResult {
     pub assets: Vc<OutputAssets>,
@@ -82,6 +113,10 @@ pub trait ChunkingContext {
         Vc::cell(false)
     }
 
+    fn chunk_size_thresholds(self: Vc<Self>) -> Vc<ChunkSizeThresholds> {
+        ChunkSizeThresholds::default().cell()
+    }
+
     fn async_loader_chunk_item(
         &self,
         module: Vc<Box<dyn ChunkableModule>>,
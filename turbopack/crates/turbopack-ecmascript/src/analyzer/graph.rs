@@ -317,6 +317,12 @@ impl EvalContext {
         self.imports.is_esm(specified_type)
     }
 
+    /// The mark of identifiers that don't resolve to a declaration, i.e. of
+    /// free variables.
+    pub fn unresolved_mark(&self) -> Mark {
+        self.unresolved_mark
+    }
+
     fn eval_prop_name(&self, prop: &PropName) -> JsValue {
         match prop {
             PropName::Ident(ident) => ident.sym.clone().into(),
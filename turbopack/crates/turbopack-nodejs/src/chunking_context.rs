@@ -9,7 +9,7 @@ use turbopack_core::{
         availability_info::AvailabilityInfo,
         chunk_group::{make_chunk_group, MakeChunkGroupResult},
         module_id_strategies::{DevModuleIdStrategy, ModuleIdStrategy},
-        Chunk, ChunkGroupResult, ChunkItem, ChunkableModule, ChunkingContext,
+        Chunk, ChunkGroupResult, ChunkItem, ChunkSizeThresholds, ChunkableModule, ChunkingContext,
         EntryChunkGroupResult, EvaluatableAssets, MinifyType, ModuleId,
     },
     environment::Environment,
@@ -59,6 +59,11 @@ impl NodeJsChunkingContextBuilder {
         self
     }
 
+    pub fn chunk_size_thresholds(mut self, chunk_size_thresholds: ChunkSizeThresholds) -> Self {
+        self.chunking_context.chunk_size_thresholds = chunk_size_thresholds;
+        self
+    }
+
     /// Builds the chunking context.
     pub fn build(self) -> Vc<NodeJsChunkingContext> {
         NodeJsChunkingContext::new(Value::new(self.chunking_context))
@@ -66,6 +71,9 @@ impl NodeJsChunkingContextBuilder {
 }
 
 /// A chunking context for build mode.
+///
+/// Chunks are always emitted as CommonJS modules: regular chunks assign their
+/// chunk items to `module.exports` and entry chunks `require` the runtime.
 #[turbo_tasks::value(serialization = "auto_for_input")]
 #[derive(Debug, Clone, Hash)]
 pub struct NodeJsChunkingContext {
@@ -92,6 +100,8 @@ pub struct NodeJsChunkingContext {
     manifest_chunks: bool,
     /// The strategy to use for generating module ids
     module_id_strategy: Vc<Box<dyn ModuleIdStrategy>>,
+    /// The chunk item sizes at which chunks are split or merged
+    chunk_size_thresholds: ChunkSizeThresholds,
 }
 
 impl NodeJsChunkingContext {
@@ -118,6 +128,7 @@ impl NodeJsChunkingContext {
                 minify_type: MinifyType::NoMinify,
                 manifest_chunks: false,
                 module_id_strategy: Vc::upcast(DevModuleIdStrategy::new()),
+                chunk_size_thresholds: Default::default(),
             },
         }
     }
@@ -253,6 +264,11 @@ impl ChunkingContext for NodeJsChunkingContext {
         Ok(self.asset_root_path.join(asset_path.into()))
     }
 
+    #[turbo_tasks::function]
+    fn chunk_size_thresholds(&self) -> Vc<ChunkSizeThresholds> {
+        self.chunk_size_thresholds.cell()
+    }
+
     #[turbo_tasks::function]
     async fn chunk_group(
         self: Vc<Self>,
@@ -17,7 +17,7 @@ use crate::NodeJsChunkingContext;
 
 /// Production Ecmascript chunk targeting Node.js.
 #[turbo_tasks::value(shared)]
-pub(crate) struct EcmascriptBuildNodeChunk {
+pub struct EcmascriptBuildNodeChunk {
     chunking_context: Vc<NodeJsChunkingContext>,
     chunk: Vc<EcmascriptChunk>,
 }
@@ -36,6 +36,12 @@ impl EcmascriptBuildNodeChunk {
         }
         .cell()
     }
+
+    /// The chunk this output asset is generated from.
+    #[turbo_tasks::function]
+    pub fn chunk(&self) -> Vc<EcmascriptChunk> {
+        self.chunk
+    }
 }
 
 #[turbo_tasks::value_impl]